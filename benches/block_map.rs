@@ -0,0 +1,113 @@
+//! Compares neighbourhood-heavy algorithms on a row-major `DynamicMap` and a `BlockMap`.
+//!
+//! Run with `cargo bench --bench block_map`.
+
+use std::{hint::black_box, time::Instant};
+
+use tile_maps::{block::BlockMap, random::Rng, row::DynamicMap, MapMut};
+
+const SIZE: usize = 1024;
+const ROUNDS: usize = 10;
+
+/// Step Conway's game of life once, reading from `src` and writing to `dst`.
+fn life<M: MapMut<Tile = bool>>(src: &M, dst: &mut M) {
+    for y in 0..src.height() {
+        for x in 0..src.width() {
+            let mut neighbours = 0;
+            for dy in -1..=1isize {
+                for dx in -1..=1isize {
+                    if (dx, dy) != (0, 0)
+                        && src.get(x.wrapping_add_signed(dx), y.wrapping_add_signed(dy))
+                            == Some(true)
+                    {
+                        neighbours += 1;
+                    }
+                }
+            }
+            let alive = src.get(x, y) == Some(true);
+            dst.set(x, y, neighbours == 3 || (alive && neighbours == 2));
+        }
+    }
+}
+
+/// Average each tile with its neighbours in a 5 by 5 box, reading from `src` and writing to
+/// `dst`.
+fn blur<M: MapMut<Tile = f32>>(src: &M, dst: &mut M) {
+    for y in 0..src.height() {
+        for x in 0..src.width() {
+            let mut sum = 0.0;
+            for dy in -2..=2isize {
+                for dx in -2..=2isize {
+                    sum += src
+                        .get(x.wrapping_add_signed(dx), y.wrapping_add_signed(dy))
+                        .unwrap_or(0.0);
+                }
+            }
+            dst.set(x, y, sum / 25.0);
+        }
+    }
+}
+
+/// Time `ROUNDS` rounds of `step`, swapping the source and destination each round.
+fn bench<M: MapMut>(name: &str, mut a: M, mut b: M, step: fn(&M, &mut M)) {
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        step(&a, &mut b);
+        core::mem::swap(&mut a, &mut b);
+    }
+    let elapsed = start.elapsed();
+    black_box(&a);
+    println!("{name:<24} {:>10.2?} per round", elapsed / ROUNDS as u32);
+}
+
+fn random<M: MapMut>(mut map: M, mut tile: impl FnMut(&mut Rng) -> M::Tile) -> M {
+    let mut rng = Rng::new(42);
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            map.set(x, y, tile(&mut rng));
+        }
+    }
+    map
+}
+
+fn main() {
+    let alive = |rng: &mut Rng| rng.chance(0.3);
+    bench(
+        "life/DynamicMap",
+        random(DynamicMap::new(SIZE, SIZE), alive),
+        DynamicMap::new(SIZE, SIZE),
+        life,
+    );
+    bench(
+        "life/BlockMap<8>",
+        random(BlockMap::<_, 8>::new(SIZE, SIZE), alive),
+        BlockMap::new(SIZE, SIZE),
+        life,
+    );
+    bench(
+        "life/BlockMap<16>",
+        random(BlockMap::<_, 16>::new(SIZE, SIZE), alive),
+        BlockMap::new(SIZE, SIZE),
+        life,
+    );
+
+    let value = |rng: &mut Rng| rng.next_f32();
+    bench(
+        "blur/DynamicMap",
+        random(DynamicMap::new(SIZE, SIZE), value),
+        DynamicMap::new(SIZE, SIZE),
+        blur,
+    );
+    bench(
+        "blur/BlockMap<8>",
+        random(BlockMap::<_, 8>::new(SIZE, SIZE), value),
+        BlockMap::new(SIZE, SIZE),
+        blur,
+    );
+    bench(
+        "blur/BlockMap<16>",
+        random(BlockMap::<_, 16>::new(SIZE, SIZE), value),
+        BlockMap::new(SIZE, SIZE),
+        blur,
+    );
+}
@@ -0,0 +1,273 @@
+//! Maps of [`bool`]s, packed into one bit per tile.
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use crate::Map;
+
+/// The number of bits in each word of a [`BitMap`].
+const BITS: usize = u64::BITS as usize;
+
+/// A [`Map`] of [`bool`]s, stored as one bit per tile, in words of storage `S`.
+///
+/// This uses an eighth of the memory of a map of [`bool`]s, and whole maps can be combined a word
+/// at a time. Use a [`StaticBitMap`] to store the bits on the stack, or a [`DynamicBitMap`] to
+/// store them on the heap.
+///
+/// `BitMap` doesn't implement [`MapMut`][crate::MapMut], as there's no way to borrow a single
+/// bit mutably. Instead, use [`BitMap::set()`] and [`BitMap::toggle()`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitMap<S> {
+    words: S,
+    width: usize,
+    height: usize,
+}
+
+/// A [`BitMap`] stored in an array of `N` words, on the stack.
+///
+/// Use [`words()`] to find the number of words needed for a map.
+pub type StaticBitMap<const N: usize> = BitMap<[u64; N]>;
+
+/// A [`BitMap`] stored on the heap.
+#[cfg(feature = "alloc")]
+pub type DynamicBitMap = BitMap<Vec<u64>>;
+
+/// Get the number of words needed to store a [`BitMap`] of the given size.
+pub const fn words(width: usize, height: usize) -> usize {
+    (width * height).div_ceil(BITS)
+}
+
+impl<const N: usize> BitMap<[u64; N]> {
+    /// Create a new `StaticBitMap`, with every tile set to [`false`].
+    ///
+    /// Returns [`None`] if `N` is less than [`words(width, height)`][words()].
+    /// # Example
+    /// ```
+    /// use tile_maps::{bitmap::{self, StaticBitMap}, prelude::*};
+    ///
+    /// let map = StaticBitMap::<{ bitmap::words(10, 10) }>::new(10, 10).unwrap();
+    /// assert_eq!(map.size(), (10, 10));
+    /// assert_eq!(map.get(3, 4), Some(false));
+    ///
+    /// assert!(StaticBitMap::<1>::new(10, 10).is_none());
+    /// ```
+    pub fn new(width: usize, height: usize) -> Option<Self> {
+        if N >= words(width, height) {
+            Some(Self {
+                words: [0; N],
+                width,
+                height,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BitMap<Vec<u64>> {
+    /// Create a new `DynamicBitMap`, with every tile set to [`false`].
+    /// # Example
+    /// ```
+    /// use tile_maps::{bitmap::DynamicBitMap, prelude::*};
+    ///
+    /// let map = DynamicBitMap::new(100, 50);
+    /// assert_eq!(map.size(), (100, 50));
+    /// assert!(!map.any());
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            words: vec![0; words(width, height)],
+            width,
+            height,
+        }
+    }
+
+    /// Create a new `DynamicBitMap` the same size as `map`, with tiles set to [`true`] where the
+    /// predicate returns [`true`].
+    /// # Example
+    /// ```
+    /// use tile_maps::{bitmap::DynamicBitMap, row::StaticMap, prelude::*};
+    ///
+    /// let map = StaticMap::from([['#', '.', '#'], ['.', '.', '#']]);
+    /// let walls = DynamicBitMap::from_map(&map, |&t| t == '#');
+    /// assert_eq!(walls.count_ones(), 3);
+    /// assert_eq!(walls.get(2, 1), Some(true));
+    /// ```
+    pub fn from_map<M, F>(map: &M, mut pred: F) -> Self
+    where
+        M: Map,
+        F: FnMut(&M::Tile) -> bool,
+    {
+        let mut bits = Self::new(map.width(), map.height());
+        for y in 0..map.height() {
+            for x in 0..map.width() {
+                if pred(map.get_ref(x, y).expect("Coordinates are in bounds")) {
+                    bits.set(x, y, true);
+                }
+            }
+        }
+        bits
+    }
+}
+
+impl<S: AsRef<[u64]>> BitMap<S> {
+    /// Get the words storing the tiles, in row-major order, starting from the least significant
+    /// bit. Bits past the end of the map are always zero.
+    pub fn words(&self) -> &[u64] {
+        &self.words.as_ref()[..words(self.width, self.height)]
+    }
+
+    /// Count the tiles set to [`true`].
+    pub fn count_ones(&self) -> usize {
+        self.words().iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Test if any tile is [`true`].
+    pub fn any(&self) -> bool {
+        self.words().iter().any(|&w| w != 0)
+    }
+
+    /// Test if every tile is [`true`]. Returns [`true`] for an empty map.
+    pub fn all(&self) -> bool {
+        self.count_ones() == self.width * self.height
+    }
+
+    /// Get the word and mask of the bit storing a tile.
+    fn bit(&self, x: usize, y: usize) -> Option<(usize, u64)> {
+        if self.in_bounds(x, y) {
+            let i = x + y * self.width;
+            Some((i / BITS, 1 << (i % BITS)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<S: AsRef<[u64]> + AsMut<[u64]>> BitMap<S> {
+    /// If the coordinates are in bounds of the map, sets that tile to `value` and returns
+    /// [`true`]. Returns [`false`] if the coordinates were out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: bool) -> bool {
+        if let Some((word, mask)) = self.bit(x, y) {
+            let word = &mut self.words.as_mut()[word];
+            if value {
+                *word |= mask;
+            } else {
+                *word &= !mask;
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// If the coordinates are in bounds of the map, flips that tile and returns its new value.
+    /// Returns [`None`] if the coordinates were out of bounds.
+    pub fn toggle(&mut self, x: usize, y: usize) -> Option<bool> {
+        let (word, mask) = self.bit(x, y)?;
+        let word = &mut self.words.as_mut()[word];
+        *word ^= mask;
+        Some(*word & mask != 0)
+    }
+
+    /// Set every tile to `value`.
+    pub fn fill(&mut self, value: bool) {
+        let fill = if value { u64::MAX } else { 0 };
+        self.words_mut().fill(fill);
+        self.clear_padding();
+    }
+
+    /// Flip every tile.
+    pub fn not(&mut self) {
+        for word in self.words_mut() {
+            *word = !*word;
+        }
+        self.clear_padding();
+    }
+
+    /// Set each tile to the logical and of it and the same tile in `other`.
+    ///
+    /// Returns [`false`], without changing anything, if the maps are different sizes.
+    /// # Example
+    /// ```
+    /// use tile_maps::{bitmap::DynamicBitMap, prelude::*};
+    ///
+    /// let mut a = DynamicBitMap::new(3, 1);
+    /// a.set(0, 0, true);
+    /// a.set(1, 0, true);
+    /// let mut b = DynamicBitMap::new(3, 1);
+    /// b.set(1, 0, true);
+    /// b.set(2, 0, true);
+    ///
+    /// assert!(a.and(&b));
+    /// assert_eq!(a.count_ones(), 1);
+    /// assert_eq!(a.get(1, 0), Some(true));
+    ///
+    /// assert!(!a.and(&DynamicBitMap::new(2, 2)));
+    /// ```
+    pub fn and<O: AsRef<[u64]>>(&mut self, other: &BitMap<O>) -> bool {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Set each tile to the logical or of it and the same tile in `other`.
+    ///
+    /// Returns [`false`], without changing anything, if the maps are different sizes.
+    pub fn or<O: AsRef<[u64]>>(&mut self, other: &BitMap<O>) -> bool {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Set each tile to the logical exclusive or of it and the same tile in `other`.
+    ///
+    /// Returns [`false`], without changing anything, if the maps are different sizes.
+    pub fn xor<O: AsRef<[u64]>>(&mut self, other: &BitMap<O>) -> bool {
+        self.combine(other, |a, b| a ^ b)
+    }
+
+    fn combine<O, F>(&mut self, other: &BitMap<O>, mut f: F) -> bool
+    where
+        O: AsRef<[u64]>,
+        F: FnMut(u64, u64) -> u64,
+    {
+        if self.size() != other.size() {
+            return false;
+        }
+        for (word, &other) in self.words_mut().iter_mut().zip(other.words()) {
+            *word = f(*word, other);
+        }
+        true
+    }
+
+    fn words_mut(&mut self) -> &mut [u64] {
+        let len = words(self.width, self.height);
+        &mut self.words.as_mut()[..len]
+    }
+
+    /// Zero the bits in the last word past the end of the map.
+    fn clear_padding(&mut self) {
+        let used = (self.width * self.height) % BITS;
+        if let (Some(last), true) = (self.words_mut().last_mut(), used != 0) {
+            *last &= (1 << used) - 1;
+        }
+    }
+}
+
+impl<S: AsRef<[u64]>> Map for BitMap<S> {
+    type Tile = bool;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile> {
+        let (word, mask) = self.bit(x, y)?;
+        Some(self.words.as_ref()[word] & mask != 0)
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        self.get(x, y).map(|bit| if bit { &true } else { &false })
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
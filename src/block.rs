@@ -0,0 +1,139 @@
+//! Maps that store their tiles in small square blocks, for cache-friendly neighbourhood access.
+
+use alloc::vec::Vec;
+
+use crate::{Map, MapMut};
+
+/// A [`Map`] that stores its tiles in square blocks of `SIZE` by `SIZE` tiles.
+///
+/// Each block is stored contiguously, so tiles that are near each other vertically, as well as
+/// horizontally, are usually near each other in memory. This makes algorithms that look at the
+/// neighbourhood of every tile, like cellular automata and [convolution][crate::convolution],
+/// friendlier to the cache than with a row-major [`DynamicMap`][crate::row::DynamicMap].
+///
+/// `SIZE` should be a power of two, so finding a tile is cheap. Maps that aren't a multiple of
+/// `SIZE` are padded to fill the blocks on their edges.
+#[derive(Clone, Debug)]
+pub struct BlockMap<T, const SIZE: usize = 8> {
+    tiles: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T, const SIZE: usize> BlockMap<T, SIZE> {
+    /// Create a new `BlockMap`. Each tile will be initialised to the default tile.
+    ///
+    /// Panics if `SIZE` is zero.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{block::BlockMap, prelude::*};
+    /// let mut map = BlockMap::<u8>::new(100, 50);
+    /// assert_eq!(map.size(), (100, 50));
+    /// map.set(99, 49, 7);
+    /// assert_eq!(map.get(99, 49), Some(7));
+    /// assert_eq!(map.get(100, 49), None);
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: Default,
+    {
+        assert!(SIZE > 0, "Blocks must contain at least one tile");
+        let blocks = width.div_ceil(SIZE) * height.div_ceil(SIZE);
+        Self {
+            tiles: core::iter::repeat_with(T::default)
+                .take(blocks * SIZE * SIZE)
+                .collect(),
+            width,
+            height,
+        }
+    }
+
+    /// Get the tiles of the block at block coordinates `(x, y)`, in row-major order, or [`None`]
+    /// if the block is out of bounds. Tiles past the edges of the map are padding.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{block::BlockMap, prelude::*};
+    /// let mut map = BlockMap::<u8, 4>::new(10, 10);
+    /// map.set(5, 6, 1);
+    /// assert_eq!(map.block(1, 1).unwrap()[1 + 2 * 4], 1);
+    /// assert!(map.block(3, 0).is_none());
+    /// ```
+    pub fn block(&self, x: usize, y: usize) -> Option<&[T]> {
+        let start = self.block_start(x, y)?;
+        Some(&self.tiles[start..start + SIZE * SIZE])
+    }
+
+    /// Get the tiles of the block at block coordinates `(x, y)` mutably, in row-major order, or
+    /// [`None`] if the block is out of bounds. Tiles past the edges of the map are padding.
+    pub fn block_mut(&mut self, x: usize, y: usize) -> Option<&mut [T]> {
+        let start = self.block_start(x, y)?;
+        Some(&mut self.tiles[start..start + SIZE * SIZE])
+    }
+
+    /// Get the index of the first tile of a block.
+    fn block_start(&self, x: usize, y: usize) -> Option<usize> {
+        let columns = self.width.div_ceil(SIZE);
+        if x < columns && y < self.height.div_ceil(SIZE) {
+            Some((x + y * columns) * SIZE * SIZE)
+        } else {
+            None
+        }
+    }
+
+    /// Get the index of a tile.
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        if self.in_bounds(x, y) {
+            let block = x / SIZE + y / SIZE * self.width.div_ceil(SIZE);
+            Some(block * SIZE * SIZE + x % SIZE + y % SIZE * SIZE)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const SIZE: usize> Map for BlockMap<T, SIZE> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        self.index(x, y).map(|i| &self.tiles[i])
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<T, const SIZE: usize> MapMut for BlockMap<T, SIZE> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Tile> {
+        self.index(x, y).map(|i| &mut self.tiles[i])
+    }
+
+    fn clear(&mut self)
+    where
+        Self::Tile: Default,
+    {
+        for tile in self.tiles.iter_mut() {
+            *tile = Self::Tile::default();
+        }
+    }
+
+    fn clear_to(&mut self, new: Self::Tile)
+    where
+        Self::Tile: Clone,
+    {
+        for tile in self.tiles.iter_mut() {
+            *tile = new.clone();
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Maps composed of shared, copy-on-write chunks.
+
+use alloc::{sync::Arc, vec, vec::Vec};
+
+use crate::{Map, MapMut};
+
+/// A [`Map`] made of square chunks of `SIZE` by `SIZE` tiles, which are shared between clones of
+/// the map until they're written to.
+///
+/// Cloning a `ChunkMap` only copies a reference to each chunk. A chunk is copied the first time
+/// it's written to while it's shared, so snapshots of a map, for rollback or undo, only cost the
+/// memory of the chunks that changed since. Chunks are reference counted with [`Arc`], so maps
+/// can be shared between threads.
+///
+/// Maps that aren't a multiple of `SIZE` are padded to fill the chunks on their edges.
+#[derive(Clone, Debug)]
+pub struct ChunkMap<T, const SIZE: usize = 16> {
+    chunks: Vec<Arc<Vec<T>>>,
+    width: usize,
+    height: usize,
+}
+
+impl<T, const SIZE: usize> ChunkMap<T, SIZE> {
+    /// Create a new `ChunkMap`. Each tile will be initialised to the default tile.
+    ///
+    /// Every chunk starts out shared, so a new map only stores a single chunk. Panics if `SIZE`
+    /// is zero.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{chunk::ChunkMap, prelude::*};
+    /// let mut map = ChunkMap::<u8>::new(100, 100);
+    /// map.set(10, 20, 1);
+    ///
+    /// let snapshot = map.clone();
+    /// map.set(90, 90, 2);
+    /// assert_eq!(snapshot.get(90, 90), Some(0));
+    /// assert_eq!(map.get(90, 90), Some(2));
+    /// assert_eq!(map.get(10, 20), Some(1));
+    ///
+    /// // Only the chunk written to since the snapshot was copied
+    /// assert_eq!(map.shared_chunks(&snapshot), 48);
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: Default + Clone,
+    {
+        assert!(SIZE > 0, "Chunks must contain at least one tile");
+        let chunk = Arc::new(vec![T::default(); SIZE * SIZE]);
+        Self {
+            chunks: vec![chunk; width.div_ceil(SIZE) * height.div_ceil(SIZE)],
+            width,
+            height,
+        }
+    }
+
+    /// Get the tiles of the chunk at chunk coordinates `(x, y)`, in row-major order, or [`None`]
+    /// if the chunk is out of bounds. Tiles past the edges of the map are padding.
+    pub fn chunk(&self, x: usize, y: usize) -> Option<&[T]> {
+        let columns = self.width.div_ceil(SIZE);
+        if x < columns && y < self.height.div_ceil(SIZE) {
+            Some(&self.chunks[x + y * columns])
+        } else {
+            None
+        }
+    }
+
+    /// Count the chunks at the same position in this map and `other` that share the same
+    /// storage, and so haven't been written to since one map was cloned from the other.
+    pub fn shared_chunks(&self, other: &Self) -> usize {
+        self.chunks
+            .iter()
+            .zip(&other.chunks)
+            .filter(|(a, b)| Arc::ptr_eq(a, b))
+            .count()
+    }
+
+    /// Get the index of the chunk containing a tile, and the index of the tile in the chunk.
+    fn index(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        if self.in_bounds(x, y) {
+            let chunk = x / SIZE + y / SIZE * self.width.div_ceil(SIZE);
+            Some((chunk, x % SIZE + y % SIZE * SIZE))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const SIZE: usize> Map for ChunkMap<T, SIZE> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        let (chunk, i) = self.index(x, y)?;
+        Some(&self.chunks[chunk][i])
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<T: Clone, const SIZE: usize> MapMut for ChunkMap<T, SIZE> {
+    /// Get a mutable reference to a tile, copying its chunk first if it's shared.
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Tile> {
+        let (chunk, i) = self.index(x, y)?;
+        Some(&mut Arc::make_mut(&mut self.chunks[chunk])[i])
+    }
+
+    fn clear(&mut self)
+    where
+        Self::Tile: Default,
+    {
+        self.clear_to(T::default());
+    }
+
+    fn clear_to(&mut self, new: Self::Tile) {
+        let chunk = Arc::new(vec![new; SIZE * SIZE]);
+        for c in self.chunks.iter_mut() {
+            *c = chunk.clone();
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Moving axis-aligned boxes through [`Map`]s, and resolving their collisions with tiles.
+//!
+//! Boxes live in continuous space, where the tile at `(x, y)` covers the square from `(x, y)` to
+//! `(x + 1, y + 1)`, and the y axis points down. Scale your positions and velocities to tile
+//! units before moving a box.
+//!
+//! Movement is swept along each axis in turn, x first, checking every tile the box passes into,
+//! so fast-moving boxes can't tunnel through thin walls. Tiles outside the bounds of the map are
+//! treated as [`Collider::Empty`].
+
+use core::ops::RangeInclusive;
+
+use crate::Map;
+
+/// How a tile collides with moving boxes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Collider {
+    /// The tile doesn't collide.
+    #[default]
+    Empty,
+    /// The tile blocks movement from every direction.
+    Solid,
+    /// The tile is a platform that blocks movement down onto its top, but can be passed through
+    /// from below or the sides.
+    OneWay,
+    /// The tile is a floor slope, that boxes are pushed up onto when their bottom centre is inside
+    /// the tile, and below its surface.
+    Slope {
+        /// The height of the surface at the left edge of the tile, from 0 at the bottom of the
+        /// tile to 1 at the top.
+        left: f32,
+        /// The height of the surface at the right edge of the tile, from 0 at the bottom of the
+        /// tile to 1 at the top.
+        right: f32,
+    },
+}
+
+/// An axis-aligned box, with its top-left corner at `(x, y)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Aabb {
+    /// The x coordinate of the left of the box.
+    pub x: f32,
+    /// The y coordinate of the top of the box.
+    pub y: f32,
+    /// The width of the box.
+    pub width: f32,
+    /// The height of the box.
+    pub height: f32,
+}
+
+impl Aabb {
+    /// Create a new `Aabb`.
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the x coordinate of the right of the box.
+    pub fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    /// Returns the y coordinate of the bottom of the box.
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+}
+
+/// The sides of a box that touched a tile while moving.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Contacts {
+    /// The box was stopped moving left.
+    pub left: bool,
+    /// The box was stopped moving right.
+    pub right: bool,
+    /// The box was stopped moving up.
+    pub top: bool,
+    /// The box was stopped moving down, or pushed up onto a slope. Useful for checking if a
+    /// character is on the ground.
+    pub bottom: bool,
+}
+
+/// The result of moving a box, as returned by [`move_aabb()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Motion {
+    /// The box, at its resolved position.
+    pub aabb: Aabb,
+    /// The velocity, with the component along each blocked axis set to zero.
+    pub velocity: (f32, f32),
+    /// The sides of the box that touched a tile.
+    pub contacts: Contacts,
+}
+
+/// Move `aabb` through `map` by `velocity`, stopping it at any tiles it collides with.
+///
+/// `classify` is called to find out how each tile collides. `velocity` is the total distance to
+/// move, so multiply it by your time step first. Only tiles inside the map are checked, so even
+/// huge or infinite velocities take no longer than crossing the map.
+///
+/// Boxes are never pulled down onto the ground. To keep a box on a slope as it walks downhill,
+/// apply gravity every frame, so it falls at least as far as the slope drops, and it will land
+/// back on the slope.
+/// # Example
+/// ```
+/// use tile_maps::{collision::{self, Aabb, Collider}, row::StaticMap};
+///
+/// let map = StaticMap::from([
+///     ['.', '.', '.', '.', '.', '#'],
+///     ['.', '.', '.', '.', '.', '#'],
+///     ['.', '.', '-', '-', '.', '#'],
+///     ['#', '#', '#', '#', '#', '#'],
+/// ]);
+/// let classify = |tile: &char| match tile {
+///     '#' => Collider::Solid,
+///     '-' => Collider::OneWay,
+///     _ => Collider::Empty,
+/// };
+///
+/// // Falling onto the floor
+/// let player = Aabb::new(0.25, 0.0, 0.5, 1.0);
+/// let motion = collision::move_aabb(&map, player, (0.0, 5.0), classify);
+/// assert_eq!(motion.aabb.y, 2.0);
+/// assert_eq!(motion.velocity, (0.0, 0.0));
+/// assert!(motion.contacts.bottom);
+///
+/// // Jumping up through a one-way platform, then landing on it
+/// let player = Aabb::new(2.25, 2.0, 0.5, 1.0);
+/// let motion = collision::move_aabb(&map, player, (0.0, -1.5), classify);
+/// assert_eq!(motion.aabb.y, 0.5);
+/// let motion = collision::move_aabb(&map, motion.aabb, (0.0, 1.0), classify);
+/// assert_eq!(motion.aabb.y, 1.0);
+/// assert!(motion.contacts.bottom);
+///
+/// // Moving fast enough to skip the wall in a single frame doesn't tunnel through it
+/// let player = Aabb::new(0.25, 1.0, 0.5, 1.0);
+/// let motion = collision::move_aabb(&map, player, (50.0, 0.0), classify);
+/// assert_eq!(motion.aabb.right(), 5.0);
+/// assert!(motion.contacts.right);
+/// ```
+/// Walking up a slope:
+/// ```
+/// use tile_maps::{collision::{self, Aabb, Collider}, row::StaticMap};
+///
+/// let map = StaticMap::from([['.', '/'], ['#', '#']]);
+/// let classify = |tile: &char| match tile {
+///     '#' => Collider::Solid,
+///     '/' => Collider::Slope { left: 0.0, right: 1.0 },
+///     _ => Collider::Empty,
+/// };
+///
+/// let player = Aabb::new(0.0, 0.0, 0.5, 1.0);
+/// let motion = collision::move_aabb(&map, player, (1.0, 0.0), classify);
+/// assert_eq!(motion.aabb.x, 1.0);
+/// assert_eq!(motion.aabb.bottom(), 0.75);
+/// assert!(motion.contacts.bottom);
+///
+/// // Walking back down, with gravity keeping the player on the slope
+/// let motion = collision::move_aabb(&map, motion.aabb, (-0.5, 1.0), classify);
+/// assert_eq!(motion.aabb.bottom(), 1.0);
+/// assert!(motion.contacts.bottom);
+/// ```
+/// Walking up a slope onto a platform at the top:
+/// ```
+/// use tile_maps::{collision::{self, Aabb, Collider}, row::StaticMap};
+///
+/// let map = StaticMap::from([
+///     ['.', '.', '.', '.'],
+///     ['.', '/', '#', '#'],
+///     ['#', '#', '#', '#'],
+/// ]);
+/// let classify = |tile: &char| match tile {
+///     '#' => Collider::Solid,
+///     '/' => Collider::Slope { left: 0.0, right: 1.0 },
+///     _ => Collider::Empty,
+/// };
+///
+/// let mut player = Aabb::new(0.0, 1.0, 0.5, 1.0);
+/// for _ in 0..8 {
+///     let motion = collision::move_aabb(&map, player, (0.25, 0.125), classify);
+///     assert!(!motion.contacts.right);
+///     player = motion.aabb;
+/// }
+/// assert_eq!(player.x, 2.0);
+/// assert_eq!(player.bottom(), 1.0);
+/// ```
+/// Falling fast onto a slope lands on it, rather than passing through:
+/// ```
+/// use tile_maps::{collision::{self, Aabb, Collider}, row::DynamicMap, prelude::*};
+///
+/// let mut map = DynamicMap::new(3, 12);
+/// map.set(1, 3, Collider::Slope { left: 0.0, right: 1.0 });
+/// map.region_mut(0, 11, 3, 1).unwrap().clear_to(Collider::Solid);
+///
+/// let player = Aabb::new(1.0, 0.0, 0.5, 1.0);
+/// let motion = collision::move_aabb(&map, player, (0.0, 10.0), |&tile| tile);
+/// assert_eq!(motion.aabb.bottom(), 3.75);
+/// assert!(motion.contacts.bottom);
+/// ```
+pub fn move_aabb<M, F>(map: &M, aabb: Aabb, velocity: (f32, f32), mut classify: F) -> Motion
+where
+    M: Map,
+    F: FnMut(&M::Tile) -> Collider,
+{
+    let mut collider = |x: isize, y: isize| {
+        usize::try_from(x)
+            .ok()
+            .zip(usize::try_from(y).ok())
+            .and_then(|(x, y)| map.get_ref(x, y))
+            .map_or(Collider::Empty, &mut classify)
+    };
+    let mut motion = Motion {
+        aabb,
+        velocity,
+        contacts: Contacts::default(),
+    };
+    let Motion {
+        aabb: ref mut b,
+        ref mut contacts,
+        ..
+    } = motion;
+    let (dx, dy) = velocity;
+
+    // Horizontal movement
+    let (top, mut bottom) = span(b.y, b.height);
+    // A box standing on a slope that rises to the top of its tile, in the direction it's moving,
+    // steps up onto solid tiles beside the slope, rather than treating them as walls
+    let centre = b.x + b.width / 2.0;
+    let step = match collider(floor(centre), bottom) {
+        Collider::Slope { left, right }
+            if (dx > 0.0 && right >= 1.0) || (dx < 0.0 && left >= 1.0) =>
+        {
+            Some(bottom)
+        }
+        _ => None,
+    };
+    if step.is_some() {
+        bottom -= 1;
+    }
+    let rows = within(top, bottom, map.height());
+    let mut blocked = |column: isize| {
+        rows.clone()
+            .any(|row| collider(column, row) == Collider::Solid)
+    };
+    if dx > 0.0 {
+        let first = ceil(b.right());
+        let last = ceil(b.right() + dx).saturating_sub(1);
+        if let Some(column) = within(first, last, map.width()).find(|&c| blocked(c)) {
+            b.x = column as f32 - b.width;
+            contacts.right = true;
+        } else {
+            b.x += dx;
+        }
+    } else if dx < 0.0 {
+        let first = floor(b.x).saturating_sub(1);
+        let last = floor(b.x + dx);
+        if let Some(column) = within(last, first, map.width()).rev().find(|&c| blocked(c)) {
+            b.x = (column + 1) as f32;
+            contacts.left = true;
+        } else {
+            b.x += dx;
+        }
+    }
+    if let Some(row) = step {
+        let centre = b.x + b.width / 2.0;
+        if collider(floor(centre), row) == Collider::Solid && b.bottom() > row as f32 {
+            b.y = row as f32 - b.height;
+            contacts.bottom = true;
+        }
+    }
+
+    // Vertical movement
+    let (left, right) = span(b.x, b.width);
+    let columns = within(left, right, map.width());
+    if dy > 0.0 {
+        let first = ceil(b.bottom());
+        let last = ceil(b.bottom() + dy).saturating_sub(1);
+        let landed = within(first, last, map.height())
+            .find(|&row| {
+                columns.clone().any(|column| {
+                    matches!(collider(column, row), Collider::Solid | Collider::OneWay)
+                })
+            })
+            .map(|row| row as f32);
+        // Slopes are swept too, under the bottom centre of the box, so falls can't skip them
+        let centre = b.x + b.width / 2.0;
+        let column = floor(centre);
+        let sloped = within(floor(b.bottom()), last, map.height()).find_map(|row| {
+            match collider(column, row) {
+                Collider::Slope { left, right } => {
+                    let surface = slope_surface(row, left, right, centre - column as f32);
+                    (b.bottom() <= surface && b.bottom() + dy > surface).then_some(surface)
+                }
+                _ => None,
+            }
+        });
+        let landed = match (landed, sloped) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        if let Some(surface) = landed {
+            b.y = surface - b.height;
+            contacts.bottom = true;
+        } else {
+            b.y += dy;
+        }
+    } else if dy < 0.0 {
+        let first = floor(b.y).saturating_sub(1);
+        let last = floor(b.y + dy);
+        let bumped = within(last, first, map.height()).rev().find(|&row| {
+            columns
+                .clone()
+                .any(|column| collider(column, row) == Collider::Solid)
+        });
+        if let Some(row) = bumped {
+            b.y = (row + 1) as f32;
+            contacts.top = true;
+        } else {
+            b.y += dy;
+        }
+    }
+
+    // Slopes
+    if dy >= 0.0 {
+        let centre = b.x + b.width / 2.0;
+        let column = floor(centre);
+        let (top, bottom) = span(b.y, b.height);
+        for row in within(top, bottom, map.height()) {
+            if let Collider::Slope { left, right } = collider(column, row) {
+                let surface = slope_surface(row, left, right, centre - column as f32);
+                if b.bottom() > surface {
+                    b.y = surface - b.height;
+                    contacts.bottom = true;
+                }
+                break;
+            }
+        }
+    }
+
+    if contacts.left || contacts.right {
+        motion.velocity.0 = 0.0;
+    }
+    if contacts.top || contacts.bottom {
+        motion.velocity.1 = 0.0;
+    }
+    motion
+}
+
+/// The y coordinate of the surface of a slope tile in `row`, `along` the way across it.
+fn slope_surface(row: isize, left: f32, right: f32, along: f32) -> f32 {
+    (row + 1) as f32 - (left + (right - left) * along)
+}
+
+/// The first and last tiles (inclusive) overlapped by a span of length `len` from `start`.
+fn span(start: f32, len: f32) -> (isize, isize) {
+    (floor(start), ceil(start + len).saturating_sub(1))
+}
+
+/// Clamp an inclusive range of tiles to the `len` tiles along one axis of the map, as tiles
+/// outside it never collide.
+fn within(first: isize, last: isize, len: usize) -> RangeInclusive<isize> {
+    first.max(0)..=last.min(isize::try_from(len).unwrap_or(isize::MAX) - 1)
+}
+
+fn floor(x: f32) -> isize {
+    libm::floorf(x) as isize
+}
+
+fn ceil(x: f32) -> isize {
+    libm::ceilf(x) as isize
+}
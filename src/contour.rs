@@ -0,0 +1,290 @@
+//! Tracing the outlines of areas of a [`Map`] into polygons.
+//!
+//! Outlines follow the edges between tiles, so their points are at the *corners* of tiles: the
+//! point `(x, y)` is the top-left corner of the tile at `(x, y)`, and a map of width `w` and height
+//! `h` has corners from `(0, 0)` to `(w, h)` inclusive.
+//!
+//! Tiles that only touch diagonally are treated as separate areas, each with their own outline.
+//!
+//! Outlines are wound clockwise when the y axis points down the screen, and holes are wound
+//! anti-clockwise. Only the points where the outline changes direction are kept.
+
+use alloc::{vec, vec::Vec};
+
+use crate::Map;
+
+/// The directions an edge can travel in, as `(dx, dy)` offsets, clockwise from right.
+const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// A closed polygon outlining an area of a map, which may have holes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    /// The points around the outside of the area.
+    pub outline: Vec<(usize, usize)>,
+    /// The points around each hole in the area.
+    pub holes: Vec<Vec<(usize, usize)>>,
+}
+
+impl Polygon {
+    /// Simplify the outline and holes of this polygon with the Ramer-Douglas-Peucker algorithm.
+    ///
+    /// Points are removed as long as the simplified outline stays within `epsilon` tiles of the
+    /// original. The remaining points are a subset of the original points.
+    /// # Example
+    /// ```
+    /// use tile_maps::{contour, row::StaticMap};
+    ///
+    /// // A staircase
+    /// let map = StaticMap::from([
+    ///     [1, 0, 0, 0],
+    ///     [1, 1, 0, 0],
+    ///     [1, 1, 1, 0],
+    ///     [1, 1, 1, 1],
+    /// ]);
+    /// let stairs = &contour::trace(&map, |&tile| tile == 1)[0];
+    /// assert_eq!(stairs.outline.len(), 10);
+    ///
+    /// let slope = stairs.simplify(1.0);
+    /// assert_eq!(slope.outline, [(0, 0), (4, 4), (0, 4)]);
+    /// ```
+    pub fn simplify(&self, epsilon: f32) -> Self {
+        Self {
+            outline: simplify(&self.outline, epsilon),
+            holes: self.holes.iter().map(|h| simplify(h, epsilon)).collect(),
+        }
+    }
+}
+
+/// Trace the outlines of the areas of `map` matching `predicate`.
+///
+/// Each 4-connected area of matching tiles produces one [`Polygon`]. Areas inside the holes of
+/// other areas produce their own polygons.
+/// # Example
+/// ```
+/// use tile_maps::{contour, row::StaticMap};
+///
+/// let map = StaticMap::from([
+///     [1, 1, 1, 0],
+///     [1, 0, 1, 0],
+///     [1, 1, 1, 0],
+///     [0, 0, 0, 1],
+/// ]);
+/// let polygons = contour::trace(&map, |&tile| tile == 1);
+/// assert_eq!(polygons.len(), 2);
+///
+/// assert_eq!(polygons[0].outline, [(0, 0), (3, 0), (3, 3), (0, 3)]);
+/// assert_eq!(polygons[0].holes, [[(1, 1), (1, 2), (2, 2), (2, 1)]]);
+///
+/// assert_eq!(polygons[1].outline, [(3, 3), (4, 3), (4, 4), (3, 4)]);
+/// assert!(polygons[1].holes.is_empty());
+/// ```
+pub fn trace<M, F>(map: &M, mut predicate: F) -> Vec<Polygon>
+where
+    M: Map,
+    F: FnMut(&M::Tile) -> bool,
+{
+    let (width, height) = map.size();
+    let mut filled = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            filled.push(predicate(
+                map.get_ref(x, y).expect("Coordinates are in bounds"),
+            ));
+        }
+    }
+    let is_filled = |x: isize, y: isize| {
+        (0..width as isize).contains(&x)
+            && (0..height as isize).contains(&y)
+            && filled[x as usize + y as usize * width]
+    };
+
+    // A bitmask of the directions of the edges leaving each corner
+    let stride = width + 1;
+    let mut edges = vec![0u8; stride * (height + 1)];
+    for y in 0..height {
+        for x in 0..width {
+            if !filled[x + y * width] {
+                continue;
+            }
+            let (ix, iy) = (x as isize, y as isize);
+            if !is_filled(ix, iy - 1) {
+                edges[x + y * stride] |= 1 << 0;
+            }
+            if !is_filled(ix + 1, iy) {
+                edges[x + 1 + y * stride] |= 1 << 1;
+            }
+            if !is_filled(ix, iy + 1) {
+                edges[x + 1 + (y + 1) * stride] |= 1 << 2;
+            }
+            if !is_filled(ix - 1, iy) {
+                edges[x + (y + 1) * stride] |= 1 << 3;
+            }
+        }
+    }
+
+    let mut visited = vec![0u8; edges.len()];
+    let mut outlines = Vec::new();
+    let mut holes = Vec::new();
+    for start in 0..edges.len() {
+        while let Some(dir) = (0..4).find(|d| (edges[start] & !visited[start]) & (1 << d) != 0) {
+            let contour = follow(&edges, &mut visited, stride, start, dir);
+            if signed_area(&contour) > 0 {
+                outlines.push(contour);
+            } else {
+                holes.push(contour);
+            }
+        }
+    }
+
+    let mut polygons = outlines
+        .into_iter()
+        .map(|outline| Polygon {
+            outline,
+            holes: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    for hole in holes {
+        // A hole belongs to the smallest outline that contains it
+        let (x, y) = hole[0];
+        let parent = polygons
+            .iter_mut()
+            .filter(|p| contains(&p.outline, x, y))
+            .min_by_key(|p| signed_area(&p.outline));
+        if let Some(parent) = parent {
+            parent.holes.push(hole);
+        }
+    }
+    polygons
+}
+
+/// Follow the edges from the corner at index `start`, leaving in direction `start_dir`, until
+/// arriving back at the same edge. Returns the corners where the direction changes.
+fn follow(
+    edges: &[u8],
+    visited: &mut [u8],
+    stride: usize,
+    start: usize,
+    start_dir: usize,
+) -> Vec<(usize, usize)> {
+    let mut points = Vec::new();
+    let (mut corner, mut dir) = (start, start_dir);
+    let mut last_dir = None;
+    loop {
+        visited[corner] |= 1 << dir;
+        if last_dir != Some(dir) {
+            points.push((corner % stride, corner / stride));
+        }
+        last_dir = Some(dir);
+
+        let (dx, dy) = DIRECTIONS[dir];
+        corner = (corner as isize + dx + dy * stride as isize) as usize;
+        // Turning right first keeps us hugging the same tile, so tiles that only touch
+        // diagonally are kept apart
+        dir = [1, 0, 3]
+            .into_iter()
+            .map(|turn| (dir + turn) % 4)
+            .find(|&d| edges[corner] & (1 << d) != 0)
+            .expect("Outlines are closed");
+        if (corner, dir) == (start, start_dir) {
+            if last_dir == Some(start_dir) {
+                // The start point is in the middle of a straight edge
+                points.remove(0);
+            }
+            return points;
+        }
+    }
+}
+
+/// Twice the signed area of a polygon. Positive for outlines, negative for holes.
+fn signed_area(points: &[(usize, usize)]) -> isize {
+    let mut area = 0;
+    for (i, &(x0, y0)) in points.iter().enumerate() {
+        let (x1, y1) = points[(i + 1) % points.len()];
+        area += x0 as isize * y1 as isize - x1 as isize * y0 as isize;
+    }
+    area
+}
+
+/// Test if the corner `(x, y)` is inside the polygon formed by `points`.
+///
+/// The corner must not lie on the polygon's edges, which is always the case for a hole tested
+/// against an outline it doesn't share edges with.
+fn contains(points: &[(usize, usize)], x: usize, y: usize) -> bool {
+    let mut inside = false;
+    for (i, &(x0, y0)) in points.iter().enumerate() {
+        let (x1, y1) = points[(i + 1) % points.len()];
+        // Edges are axis-aligned, so only vertical ones can cross a horizontal ray
+        if x0 == x1 && (y0 > y) != (y1 > y) && x < x0 {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Simplify a closed polygon with the Ramer-Douglas-Peucker algorithm.
+fn simplify(points: &[(usize, usize)], epsilon: f32) -> Vec<(usize, usize)> {
+    if points.len() < 4 {
+        return points.to_vec();
+    }
+    // Split the polygon at the point furthest from the first, and simplify each half
+    let far = (1..points.len())
+        .max_by_key(|&i| {
+            let (dx, dy) = delta(points[0], points[i]);
+            dx * dx + dy * dy
+        })
+        .expect("Polygon has points");
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[far] = true;
+    let epsilon = epsilon as f64;
+    simplify_range(points, 0, far, epsilon * epsilon, &mut keep);
+    simplify_range(points, far, points.len(), epsilon * epsilon, &mut keep);
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(&p, keep)| keep.then_some(p))
+        .collect()
+}
+
+/// Mark the points between `start` and `end` (which wraps to index 0) that must be kept.
+fn simplify_range(
+    points: &[(usize, usize)],
+    start: usize,
+    end: usize,
+    epsilon_squared: f64,
+    keep: &mut [bool],
+) {
+    if end <= start + 1 {
+        return;
+    }
+    let a = points[start];
+    let b = points[end % points.len()];
+    let (lx, ly) = delta(a, b);
+    let len_squared = (lx * lx + ly * ly) as f64;
+
+    let (index, dist_squared) = (start + 1..end)
+        .map(|i| {
+            let (px, py) = delta(a, points[i]);
+            let dist_squared = if len_squared == 0.0 {
+                (px * px + py * py) as f64
+            } else {
+                let cross = (lx * py - ly * px) as f64;
+                cross * cross / len_squared
+            };
+            (i, dist_squared)
+        })
+        .fold(
+            (start, -1.0),
+            |best, next| if next.1 > best.1 { next } else { best },
+        );
+    if dist_squared > epsilon_squared {
+        keep[index] = true;
+        simplify_range(points, start, index, epsilon_squared, keep);
+        simplify_range(points, index, end, epsilon_squared, keep);
+    }
+}
+
+/// The offset from `a` to `b`.
+fn delta(a: (usize, usize), b: (usize, usize)) -> (isize, isize) {
+    (b.0 as isize - a.0 as isize, b.1 as isize - a.1 as isize)
+}
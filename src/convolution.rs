@@ -0,0 +1,103 @@
+//! Convolution of [`Map`]s of numeric tiles with arbitrary kernels.
+//!
+//! A kernel is itself a [`Map`], centred on the tile being computed: the tile at
+//! `(kernel.width() / 2, kernel.height() / 2)` is multiplied by the source tile at the same
+//! position as the destination tile, and the rest of the kernel is laid over its neighbours.
+//! Products are summed to give the destination tile.
+//!
+//! The kernel is applied as-is, without being flipped first (strictly speaking, this is a
+//! cross-correlation). For symmetric kernels, like blurs and box sums, this makes no difference.
+//!
+//! What happens to kernel tiles that land outside the source map is controlled by an
+//! [`EdgePolicy`].
+
+use core::ops::{Add, Mul};
+
+use crate::{Map, MapMut};
+
+/// How to read tiles outside the bounds of the source map while convolving.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EdgePolicy {
+    /// Use the nearest tile on the edge of the map.
+    Clamp,
+    /// Wrap around to the opposite edge of the map, as if it were tiled infinitely.
+    Wrap,
+    /// Treat tiles outside the map as zero (the default tile).
+    Zero,
+}
+
+/// Convolve `src` with `kernel`, writing the result into `dst`.
+///
+/// Tiles that are out of bounds of `dst` are skipped. Zero is taken to be [`Default::default()`],
+/// which is the case for all the primitive numeric types.
+/// # Example
+/// Smoothing a heightmap with a box blur:
+/// ```
+/// use tile_maps::{convolution::{self, EdgePolicy}, row::{DynamicMap, StaticMap}, prelude::*};
+///
+/// let mut heights = DynamicMap::<f32>::new(3, 3);
+/// heights.set(1, 1, 9.0);
+///
+/// let mut blur = StaticMap::<f32, 3, 3>::new();
+/// blur.clear_to(1.0 / 9.0);
+///
+/// let mut smoothed = DynamicMap::<f32>::new(3, 3);
+/// convolution::convolve(&heights, &mut smoothed, &blur, EdgePolicy::Zero);
+/// assert!((smoothed.get(0, 0).unwrap() - 1.0).abs() < 1e-6);
+/// assert!((smoothed.get(1, 1).unwrap() - 1.0).abs() < 1e-6);
+/// ```
+/// Edge detection with a Laplacian kernel:
+/// ```
+/// use tile_maps::{convolution::{self, EdgePolicy}, row::StaticMap, prelude::*};
+///
+/// let walls = StaticMap::from([[0, 0, 1, 1], [0, 0, 1, 1], [0, 0, 1, 1]]);
+/// let laplacian = StaticMap::from([[0, 1, 0], [1, -4, 1], [0, 1, 0]]);
+///
+/// let mut edges = StaticMap::<i32, 4, 3>::new();
+/// convolution::convolve(&walls, &mut edges, &laplacian, EdgePolicy::Clamp);
+/// assert_eq!(edges.row(1), Some([0, 1, -1, 0].as_slice()));
+/// ```
+pub fn convolve<T, M, D, K>(src: &M, dst: &mut D, kernel: &K, edges: EdgePolicy)
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+    M: Map<Tile = T>,
+    D: MapMut<Tile = T>,
+    K: Map<Tile = T>,
+{
+    let (width, height) = src.size();
+    if width == 0 || height == 0 {
+        return;
+    }
+    let (kernel_width, kernel_height) = kernel.size();
+    let (anchor_x, anchor_y) = (kernel_width / 2, kernel_height / 2);
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = T::default();
+            for ky in 0..kernel_height {
+                for kx in 0..kernel_width {
+                    let weight = kernel.get(kx, ky).expect("Coordinates are in bounds");
+                    let sx = resolve(x as isize + kx as isize - anchor_x as isize, width, edges);
+                    let sy = resolve(y as isize + ky as isize - anchor_y as isize, height, edges);
+                    if let (Some(sx), Some(sy)) = (sx, sy) {
+                        let tile = src.get(sx, sy).expect("Coordinates are in bounds");
+                        sum = sum + tile * weight;
+                    }
+                }
+            }
+            dst.set(x, y, sum);
+        }
+    }
+}
+
+/// Resolve a coordinate along an axis of length `len` according to `edges`. Returns [`None`] if
+/// the tile should be treated as zero.
+fn resolve(coord: isize, len: usize, edges: EdgePolicy) -> Option<usize> {
+    let len = len as isize;
+    match edges {
+        _ if (0..len).contains(&coord) => Some(coord as usize),
+        EdgePolicy::Clamp => Some(coord.clamp(0, len - 1) as usize),
+        EdgePolicy::Wrap => Some(coord.rem_euclid(len) as usize),
+        EdgePolicy::Zero => None,
+    }
+}
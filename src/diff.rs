@@ -0,0 +1,148 @@
+//! Finding the differences between two [`Map`]s, and applying them as patches.
+//!
+//! A [`Patch`] records the tiles that changed between two maps, which is useful for undo
+//! history, syncing maps over a network, and reviewing changes to levels. To undo a patch, diff
+//! the maps the other way around.
+
+use alloc::vec::Vec;
+
+use crate::{
+    rect::{self, Rect},
+    row::DynamicMap,
+    Map, MapMut,
+};
+
+/// A tile that changed between two maps.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Change<T> {
+    /// The x coordinate of the tile.
+    pub x: usize,
+    /// The y coordinate of the tile.
+    pub y: usize,
+    /// The new tile.
+    pub tile: T,
+}
+
+/// The changes needed to turn one map into another, as returned by [`diff()`].
+///
+/// Apply a patch with [`MapMut::apply_patch()`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Patch<T> {
+    from: (usize, usize),
+    to: (usize, usize),
+    changes: Vec<Change<T>>,
+}
+
+impl<T> Patch<T> {
+    /// Get the size (width, height) of the map the patch applies to.
+    pub fn from_size(&self) -> (usize, usize) {
+        self.from
+    }
+
+    /// Get the size (width, height) of the map after the patch is applied.
+    pub fn to_size(&self) -> (usize, usize) {
+        self.to
+    }
+
+    /// Get the changed tiles, ordered from left to right and top to bottom.
+    pub fn changes(&self) -> &[Change<T>] {
+        &self.changes
+    }
+
+    /// Test if the patch changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.from == self.to && self.changes.is_empty()
+    }
+
+    /// Get a small set of rectangles covering the changed tiles. See [`rect::decompose()`].
+    /// # Example
+    /// ```
+    /// use tile_maps::{diff, row::DynamicMap, Rect, prelude::*};
+    ///
+    /// let before = DynamicMap::<u8>::new(10, 10);
+    /// let mut after = before.clone();
+    /// after.region_mut(2, 3, 4, 2).unwrap().clear_to(1);
+    ///
+    /// let patch = diff::diff(&before, &after);
+    /// assert_eq!(patch.changes().len(), 8);
+    /// assert_eq!(patch.rects(), [Rect::new(2, 3, 4, 2)]);
+    /// ```
+    pub fn rects(&self) -> Vec<Rect> {
+        let (width, height) = self.to;
+        let mut changed = DynamicMap::new(width, height);
+        for change in &self.changes {
+            changed.set(change.x, change.y, true);
+        }
+        rect::decompose(&changed, |&changed| changed)
+    }
+}
+
+/// Find the changes needed to turn map `a` into map `b`.
+///
+/// If the maps are different sizes, every tile of `b` outside the bounds of `a` is recorded as a
+/// change, so the patch can be applied to maps that can resize, like [`DynamicMap`].
+/// # Example
+/// ```
+/// use tile_maps::{diff, row::DynamicMap, prelude::*};
+///
+/// let before = DynamicMap::from_vec(vec![1, 2, 3, 4], 2).unwrap();
+/// let after = DynamicMap::from_vec(vec![1, 5, 3, 4, 6, 7], 2).unwrap();
+///
+/// let patch = diff::diff(&before, &after);
+/// assert_eq!(patch.changes().len(), 3);
+///
+/// let mut map = before.clone();
+/// assert!(map.apply_patch(&patch));
+/// assert_eq!(map.row(0), Some([1, 5].as_slice()));
+/// assert_eq!(map.row(2), Some([6, 7].as_slice()));
+///
+/// // Undo
+/// assert!(map.apply_patch(&diff::diff(&after, &before)));
+/// assert_eq!(map.size(), (2, 2));
+/// assert_eq!(map.row(0), Some([1, 2].as_slice()));
+/// ```
+pub fn diff<T, A, B>(a: &A, b: &B) -> Patch<T>
+where
+    T: PartialEq + Clone,
+    A: Map<Tile = T>,
+    B: Map<Tile = T>,
+{
+    let mut changes = Vec::new();
+    for y in 0..b.height() {
+        for x in 0..b.width() {
+            let tile = b.get_ref(x, y).expect("Coordinates are in bounds");
+            if a.get_ref(x, y) != Some(tile) {
+                changes.push(Change {
+                    x,
+                    y,
+                    tile: tile.clone(),
+                });
+            }
+        }
+    }
+    Patch {
+        from: a.size(),
+        to: b.size(),
+        changes,
+    }
+}
+
+/// Resize a [`DynamicMap`] to fit a patch, then apply it.
+pub(crate) fn apply_resizing<T: Clone>(map: &mut DynamicMap<T>, patch: &Patch<T>) -> bool {
+    if map.size() != patch.from {
+        return false;
+    }
+    if patch.from != patch.to {
+        let (width, height) = patch.to;
+        match patch.changes.first() {
+            // Every new tile is covered by a change, so any changed tile will do to fill the gaps
+            Some(change) => map.resize_with(width, height, || change.tile.clone()),
+            // Without changes there are no new tiles, so the map only loses tiles
+            None => map.truncate(width, height),
+        }
+    }
+    for change in &patch.changes {
+        map.set(change.x, change.y, change.tile.clone());
+    }
+    true
+}
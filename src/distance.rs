@@ -0,0 +1,266 @@
+//! Distance transforms over [`Map`]s.
+//!
+//! A distance transform finds, for every tile of a map, the distance to the nearest *feature*
+//! tile, where features are selected with a predicate. Feature tiles have a distance of zero.
+//! Each transform runs in time linear in the number of tiles.
+//!
+//! If the map contains no feature tiles, every tile is given the maximum distance for that
+//! transform ([`usize::MAX`] or [`f32::INFINITY`]).
+
+use alloc::vec::Vec;
+
+use crate::{row::DynamicMap, Map};
+
+/// Squared distance used to represent "no feature in range" while computing the Euclidean
+/// transform.
+const FAR: f64 = 1e20;
+
+/// Compute the Manhattan (taxicab) distance from each tile to the nearest feature tile.
+///
+/// Movement is only allowed horizontally and vertically, so diagonal tiles are a distance of 2
+/// apart.
+/// # Example
+/// ```
+/// use tile_maps::{distance, row::DynamicMap, prelude::*};
+///
+/// let mut map = DynamicMap::<bool>::new(5, 5);
+/// map.set(2, 2, true);
+///
+/// let distances = distance::manhattan(&map, |&wall| wall);
+/// assert_eq!(distances.get(2, 2), Some(0));
+/// assert_eq!(distances.get(3, 2), Some(1));
+/// assert_eq!(distances.get(3, 3), Some(2));
+/// assert_eq!(distances.get(0, 0), Some(4));
+/// ```
+pub fn manhattan<M, F>(map: &M, is_feature: F) -> DynamicMap<usize>
+where
+    M: Map,
+    F: FnMut(&M::Tile) -> bool,
+{
+    let (width, height) = map.size();
+    let mut dist = features(map, is_feature, 0, usize::MAX);
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut d = dist[x + y * width];
+            if x > 0 {
+                d = d.min(dist[x - 1 + y * width].saturating_add(1));
+            }
+            if y > 0 {
+                d = d.min(dist[x + (y - 1) * width].saturating_add(1));
+            }
+            dist[x + y * width] = d;
+        }
+    }
+    for y in (0..height).rev() {
+        for x in (0..width).rev() {
+            let mut d = dist[x + y * width];
+            if x + 1 < width {
+                d = d.min(dist[x + 1 + y * width].saturating_add(1));
+            }
+            if y + 1 < height {
+                d = d.min(dist[x + (y + 1) * width].saturating_add(1));
+            }
+            dist[x + y * width] = d;
+        }
+    }
+
+    into_map(dist, width)
+}
+
+/// Compute the Chebyshev (chessboard) distance from each tile to the nearest feature tile.
+///
+/// Movement is allowed in all 8 directions, so diagonal tiles are a distance of 1 apart.
+/// # Example
+/// ```
+/// use tile_maps::{distance, row::DynamicMap, prelude::*};
+///
+/// let mut map = DynamicMap::<bool>::new(5, 5);
+/// map.set(2, 2, true);
+///
+/// let distances = distance::chebyshev(&map, |&wall| wall);
+/// assert_eq!(distances.get(2, 2), Some(0));
+/// assert_eq!(distances.get(3, 3), Some(1));
+/// assert_eq!(distances.get(0, 0), Some(2));
+/// assert_eq!(distances.get(4, 0), Some(2));
+/// ```
+pub fn chebyshev<M, F>(map: &M, is_feature: F) -> DynamicMap<usize>
+where
+    M: Map,
+    F: FnMut(&M::Tile) -> bool,
+{
+    let (width, height) = map.size();
+    let mut dist = features(map, is_feature, 0, usize::MAX);
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut d = dist[x + y * width];
+            if x > 0 {
+                d = d.min(dist[x - 1 + y * width].saturating_add(1));
+            }
+            if y > 0 {
+                let above = (y - 1) * width;
+                d = d.min(dist[x + above].saturating_add(1));
+                if x > 0 {
+                    d = d.min(dist[x - 1 + above].saturating_add(1));
+                }
+                if x + 1 < width {
+                    d = d.min(dist[x + 1 + above].saturating_add(1));
+                }
+            }
+            dist[x + y * width] = d;
+        }
+    }
+    for y in (0..height).rev() {
+        for x in (0..width).rev() {
+            let mut d = dist[x + y * width];
+            if x + 1 < width {
+                d = d.min(dist[x + 1 + y * width].saturating_add(1));
+            }
+            if y + 1 < height {
+                let below = (y + 1) * width;
+                d = d.min(dist[x + below].saturating_add(1));
+                if x > 0 {
+                    d = d.min(dist[x - 1 + below].saturating_add(1));
+                }
+                if x + 1 < width {
+                    d = d.min(dist[x + 1 + below].saturating_add(1));
+                }
+            }
+            dist[x + y * width] = d;
+        }
+    }
+
+    into_map(dist, width)
+}
+
+/// Compute the exact Euclidean (straight-line) distance from each tile to the nearest feature
+/// tile.
+///
+/// This uses the separable algorithm described by Felzenszwalb and Huttenlocher in "Distance
+/// Transforms of Sampled Functions".
+/// # Example
+/// ```
+/// use tile_maps::{distance, row::DynamicMap, prelude::*};
+///
+/// let mut map = DynamicMap::<bool>::new(5, 5);
+/// map.set(0, 0, true);
+///
+/// let distances = distance::euclidean(&map, |&wall| wall);
+/// assert_eq!(distances.get(0, 0), Some(0.0));
+/// assert_eq!(distances.get(4, 0), Some(4.0));
+/// assert_eq!(distances.get(3, 4), Some(5.0));
+/// ```
+pub fn euclidean<M, F>(map: &M, is_feature: F) -> DynamicMap<f32>
+where
+    M: Map,
+    F: FnMut(&M::Tile) -> bool,
+{
+    let (width, height) = map.size();
+    let mut dist = features(map, is_feature, 0.0, FAR);
+
+    let len = width.max(height);
+    let mut f = Vec::with_capacity(len);
+    let mut d = Vec::with_capacity(len);
+    let mut v = Vec::with_capacity(len);
+    let mut z = Vec::with_capacity(len + 1);
+
+    for x in 0..width {
+        f.clear();
+        f.extend((0..height).map(|y| dist[x + y * width]));
+        transform_1d(&f, &mut d, &mut v, &mut z);
+        for (y, &squared) in d.iter().enumerate() {
+            dist[x + y * width] = squared;
+        }
+    }
+    for y in 0..height {
+        let row = &mut dist[y * width..(y + 1) * width];
+        f.clear();
+        f.extend_from_slice(row);
+        transform_1d(&f, &mut d, &mut v, &mut z);
+        row.copy_from_slice(&d);
+    }
+
+    into_map(
+        dist.into_iter()
+            .map(|squared| {
+                if squared >= FAR {
+                    f32::INFINITY
+                } else {
+                    libm::sqrt(squared) as f32
+                }
+            })
+            .collect(),
+        width,
+    )
+}
+
+/// Initialise a buffer with `feature` for each feature tile, and `other` for every other tile.
+fn features<M, F, D>(map: &M, mut is_feature: F, feature: D, other: D) -> Vec<D>
+where
+    M: Map,
+    F: FnMut(&M::Tile) -> bool,
+    D: Copy,
+{
+    let (width, height) = map.size();
+    let mut buf = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let tile = map.get_ref(x, y).expect("Coordinates are in bounds");
+            buf.push(if is_feature(tile) { feature } else { other });
+        }
+    }
+    buf
+}
+
+/// Wrap the computed distances in a [`DynamicMap`].
+fn into_map<D: Default>(dist: Vec<D>, width: usize) -> DynamicMap<D> {
+    if dist.is_empty() {
+        DynamicMap::new(width, 0)
+    } else {
+        DynamicMap::from_vec(dist, width).expect("Buffer is a multiple of the width")
+    }
+}
+
+/// One dimensional squared Euclidean distance transform of `f`, written to `d`.
+///
+/// `v` and `z` are scratch buffers for the locations and boundaries of the parabolas forming the
+/// lower envelope.
+fn transform_1d(f: &[f64], d: &mut Vec<f64>, v: &mut Vec<usize>, z: &mut Vec<f64>) {
+    d.clear();
+    v.clear();
+    z.clear();
+    if f.is_empty() {
+        return;
+    }
+
+    let intersect = |q: usize, p: usize| {
+        let (qf, pf) = (q as f64, p as f64);
+        ((f[q] + qf * qf) - (f[p] + pf * pf)) / (2.0 * qf - 2.0 * pf)
+    };
+
+    v.push(0);
+    z.push(f64::NEG_INFINITY);
+    z.push(f64::INFINITY);
+    for q in 1..f.len() {
+        let mut s = intersect(q, v[v.len() - 1]);
+        while s <= z[v.len() - 1] {
+            v.pop();
+            z.pop();
+            s = intersect(q, v[v.len() - 1]);
+        }
+        let k = v.len();
+        v.push(q);
+        z[k] = s;
+        z.push(f64::INFINITY);
+    }
+
+    let mut k = 0;
+    for q in 0..f.len() {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let offset = q as f64 - v[k] as f64;
+        d.push(offset * offset + f[v[k]]);
+    }
+}
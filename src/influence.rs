@@ -0,0 +1,204 @@
+//! Influence maps, for estimating which areas of a map are controlled or threatened by agents.
+
+use alloc::vec::Vec;
+
+use crate::{row::DynamicMap, Map, MapMut};
+
+/// How the influence stamped by an agent fades with distance. See [`InfluenceMap::stamp()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Falloff {
+    /// Full strength everywhere within the radius.
+    Constant,
+    /// Fades in a straight line, from full strength at the centre to nothing at the radius.
+    #[default]
+    Linear,
+    /// Fades quickly near the centre, then more slowly towards the radius.
+    Quadratic,
+}
+
+/// A map of the influence agents exert over each tile, backed by a [`DynamicMap<f32>`].
+///
+/// Influence is usually stamped from each agent's position, then spread across the map over time
+/// with [`propagate()`][InfluenceMap::propagate()]. Several layers, like the influence of each
+/// team, can be combined with [`merge()`][InfluenceMap::merge()].
+///
+/// `InfluenceMap` implements [`Map`] and [`MapMut`], so individual tiles can be read and written
+/// directly.
+#[derive(Clone)]
+pub struct InfluenceMap {
+    influence: DynamicMap<f32>,
+}
+
+impl InfluenceMap {
+    /// Create a new `InfluenceMap`, with no influence on any tile.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            influence: DynamicMap::new(width, height),
+        }
+    }
+
+    /// Get the influence on each tile.
+    pub fn influence(&self) -> &DynamicMap<f32> {
+        &self.influence
+    }
+
+    /// Add influence of `strength` around the tile at `(x, y)`, fading to nothing `radius` tiles
+    /// away according to `falloff`.
+    /// # Example
+    /// ```
+    /// use tile_maps::{influence::{Falloff, InfluenceMap}, prelude::*};
+    ///
+    /// let mut threat = InfluenceMap::new(10, 10);
+    /// threat.stamp(5, 5, 1.0, 4.0, Falloff::Linear);
+    /// threat.stamp(6, 5, 1.0, 4.0, Falloff::Linear);
+    /// assert_eq!(threat.get(5, 5), Some(1.0 + 0.75));
+    /// assert_eq!(threat.get(3, 5), Some(0.5 + 0.25));
+    /// assert_eq!(threat.get(0, 0), Some(0.0));
+    /// ```
+    pub fn stamp(&mut self, x: usize, y: usize, strength: f32, radius: f32, falloff: Falloff) {
+        let reach = libm::floorf(radius.max(0.0)) as usize;
+        let (width, height) = self.influence.size();
+        let right = x.saturating_add(reach).min(width.saturating_sub(1));
+        let bottom = y.saturating_add(reach).min(height.saturating_sub(1));
+        for ty in y.saturating_sub(reach)..=bottom {
+            for tx in x.saturating_sub(reach)..=right {
+                let (dx, dy) = (tx.abs_diff(x) as f32, ty.abs_diff(y) as f32);
+                let distance = libm::sqrtf(dx * dx + dy * dy);
+                if distance > radius {
+                    continue;
+                }
+                let linear = if radius > 0.0 {
+                    1.0 - distance / radius
+                } else {
+                    1.0
+                };
+                let factor = match falloff {
+                    Falloff::Constant => 1.0,
+                    Falloff::Linear => linear,
+                    Falloff::Quadratic => linear * linear,
+                };
+                if let Some(tile) = self.influence.get_mut(tx, ty) {
+                    *tile += strength * factor;
+                }
+            }
+        }
+    }
+
+    /// Spread influence across the map by one step.
+    ///
+    /// Each tile takes the strongest influence (positive or negative) from its 8 neighbours,
+    /// reduced by `decay` (from 0 to 1) for each tile of distance travelled. `momentum` (also
+    /// from 0 to 1) controls how much of the tile's existing influence is kept: the new influence
+    /// is `current * momentum + spread * (1 - momentum)`.
+    /// # Example
+    /// ```
+    /// use tile_maps::{influence::{Falloff, InfluenceMap}, prelude::*};
+    ///
+    /// let mut territory = InfluenceMap::new(5, 1);
+    /// territory.stamp(0, 0, 1.0, 0.0, Falloff::Constant);
+    /// for _ in 0..4 {
+    ///     territory.propagate(0.5, 0.0);
+    /// }
+    /// assert_eq!(territory.get(4, 0), Some(0.5 * 0.5 * 0.5 * 0.5));
+    /// ```
+    pub fn propagate(&mut self, decay: f32, momentum: f32) {
+        let (width, height) = self.influence.size();
+        let keep = 1.0 - decay;
+        let diagonal = libm::powf(keep, core::f32::consts::SQRT_2);
+        let mut next = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut spread = 0.0f32;
+                for dy in -1..=1isize {
+                    for dx in -1..=1isize {
+                        if (dx, dy) == (0, 0) {
+                            continue;
+                        }
+                        let neighbour = x
+                            .checked_add_signed(dx)
+                            .zip(y.checked_add_signed(dy))
+                            .and_then(|(x, y)| self.influence.get(x, y));
+                        let Some(neighbour) = neighbour else {
+                            continue;
+                        };
+                        let factor = if dx != 0 && dy != 0 { diagonal } else { keep };
+                        let value = neighbour * factor;
+                        if value.abs() > spread.abs() {
+                            spread = value;
+                        }
+                    }
+                }
+                let current = self.influence.get(x, y).expect("Coordinates are in bounds");
+                next.push(current * momentum + spread * (1.0 - momentum));
+            }
+        }
+        if let Some(next) = DynamicMap::from_vec(next, width) {
+            self.influence = next;
+        }
+    }
+
+    /// Add the influence from `other`, multiplied by `weight`, to this map. Returns [`false`],
+    /// without changing anything, if the maps aren't the same size.
+    ///
+    /// A `weight` of `-1.0` subtracts the other map, which is useful for finding the frontier
+    /// between two opposing teams.
+    /// # Example
+    /// ```
+    /// use tile_maps::{influence::{Falloff, InfluenceMap}, prelude::*};
+    ///
+    /// let mut red = InfluenceMap::new(9, 1);
+    /// red.stamp(0, 0, 1.0, 8.0, Falloff::Linear);
+    /// let mut blue = InfluenceMap::new(9, 1);
+    /// blue.stamp(8, 0, 1.0, 8.0, Falloff::Linear);
+    ///
+    /// let mut balance = red.clone();
+    /// assert!(balance.merge(&blue, -1.0));
+    /// assert_eq!(balance.get(4, 0), Some(0.0));
+    /// assert!(balance.get(3, 0).unwrap() > 0.0);
+    /// assert!(balance.get(5, 0).unwrap() < 0.0);
+    /// ```
+    pub fn merge<M: Map<Tile = f32>>(&mut self, other: &M, weight: f32) -> bool {
+        if other.size() != self.size() {
+            return false;
+        }
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let add = other.get(x, y).expect("Coordinates are in bounds") * weight;
+                if let Some(tile) = self.influence.get_mut(x, y) {
+                    *tile += add;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl Map for InfluenceMap {
+    type Tile = f32;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile> {
+        self.influence.get(x, y)
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        self.influence.get_ref(x, y)
+    }
+
+    fn width(&self) -> usize {
+        self.influence.width()
+    }
+
+    fn height(&self) -> usize {
+        self.influence.height()
+    }
+}
+
+impl MapMut for InfluenceMap {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Tile> {
+        self.influence.get_mut(x, y)
+    }
+
+    fn clear(&mut self) {
+        self.influence.clear();
+    }
+}
@@ -3,29 +3,58 @@
 //! * [x] Convenient API to get and set tiles
 //! * [x] Use any type that implements [`Default`] as a tile
 //! * [x] stack-allocated, fixed-size maps with [`StaticMap`][row::StaticMap].
-//! * [x] heap-allocated, dynamic maps with [`DynamicMap`][row::DynamicMap].
+//! * [x] heap-allocated, dynamic, resizable maps with [`DynamicMap`][row::DynamicMap].
 //! * [x] Borrow, mutably or immutably, [regions][region] of maps
+//! * [x] Euclidean, Manhattan and Chebyshev [distance transforms][distance]
+//! * [x] [Morphological operations][morphology]: erode, dilate, open and close
+//! * [x] [Convolution][convolution] of numeric maps with arbitrary kernels
+//! * [x] [Summed-area tables][summed_area] for constant-time rectangle sums
+//! * [x] Greedy [decomposition][rect::decompose()] of tiles into rectangles, for colliders
+//! * [x] [Contour tracing][contour] of areas into polygon outlines, with holes
+//! * [x] DDA [raycasting][raycast] with hit information
+//! * [x] Swept [collision] of boxes against solid, one-way and sloped tiles
+//! * [x] Scalar and coloured [lighting] from point sources, with incremental updates
+//! * [x] [Influence maps][influence] with falloff, decay and momentum
+//! * [x] [Voronoi][voronoi] partitioning by straight-line or walkable distance
+//! * [x] [Pattern][pattern] search and replace, with wildcards, rotations and reflections
+//! * [x] [Diffing][diff] maps, and applying the differences as patches
+//! * [x] Seedable Perlin and value [noise], with fractal octaves, for filling maps with terrain
+//! * [x] A small, seedable [random] number generator
+//! * [x] Random [walk]s, for carving caves and tunnels
+//! * [x] Memory-efficient [sparse][sparse::SparseMap] maps, that only store non-default tiles
+//! * [x] Compact [bitmap]s of [`bool`]s, with whole-map logical operations
+//! * [x] [Palette][palette]-compressed maps, for large tiles with few distinct values
+//! * [x] [Run-length encoded][rle] maps, for long horizontal runs of tiles
+//! * [x] Cache-friendly maps stored in square [block]s
+//! * [x] [Quadtree][quad] maps, that collapse large areas of identical tiles
+//! * [x] Maps composed of shared, copy-on-write [chunk]s, for cheap snapshots
+//! * [x] Immutable, [persistent] maps, that share storage between versions
+//! * [x] Borrow tiles from slices, optionally with a stride, with [`SliceMap`][row::SliceMap]
+//!   and [`SliceMapMut`][row::SliceMapMut]
+//! * [x] Fixed-capacity, resizable maps without an allocator, with [`ArrayMap`][row::ArrayMap]
 //! ## Goals
-//! * [ ] Resizable maps
 //! * [ ] Maps stored as a graph, for easier path-finding
 //! * [ ] Maps that store tiles in column-major order
-//! * [ ] Maps composed of chunks
 //! * [ ] Implement [`Index`][core::ops::Index] and [`IndexMut`][core::ops::IndexMut] for map types
 //! * [ ] `MapCursor` for representing a cursor, or a player, on a tile of a map
 //! * [ ] A MapViewport that you can use, along with your screen dimensions, to make it easy to
-//! draw the visible section of a map
+//!   draw the visible section of a map
 //! ## Questions
 //! * When borrowing a region of a region, should we borrow from the root, parent map, or from the
-//! first region?
+//!   first region?
 //! * Can we implement [`MapRows::rows`][row::MapRows::rows()] and
-//! [`MapRowsMut::rows_mut`][row::MapRowsMut::rows_mut()] wihtout adding another layer of dynamic
-//! dispatch to the iterator?
+//!   [`MapRowsMut::rows_mut`][row::MapRowsMut::rows_mut()] wihtout adding another layer of dynamic
+//!   dispatch to the iterator?
 //! * Is there any benefit, even from an API standpoint, in creating maps with interior mutability,
-//! or locking?
+//!   or locking?
 //! ## No STD
 //! This crate doesn't rely on the Rust standard library. However, by default, it does rely on
 //! [`alloc`] for types that allocate, like [`DynamicMap`][row::DynamicMap]. Disabling the "alloc"
 //! Cargo feature will relax this requirement, and remove any types that allocate.
+//!
+//! [`ChunkMap`][chunk::ChunkMap] and [`PersistentMap`][persistent::PersistentMap] share their
+//! storage with [`Arc`][alloc::sync::Arc], so they're only available on targets with atomic
+//! pointers.
 
 #![no_std]
 #![warn(missing_docs)]
@@ -33,8 +62,48 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+pub mod bitmap;
+#[cfg(feature = "alloc")]
+pub mod block;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub mod chunk;
+pub mod collision;
+#[cfg(feature = "alloc")]
+pub mod contour;
+pub mod convolution;
+#[cfg(feature = "alloc")]
+pub mod diff;
+#[cfg(feature = "alloc")]
+pub mod distance;
+#[cfg(feature = "alloc")]
+pub mod influence;
+#[cfg(feature = "alloc")]
+pub mod lighting;
+pub mod morphology;
+pub mod noise;
+#[cfg(feature = "alloc")]
+pub mod palette;
+#[cfg(feature = "alloc")]
+pub mod pattern;
+#[cfg(all(feature = "alloc", target_has_atomic = "ptr"))]
+pub mod persistent;
+#[cfg(feature = "alloc")]
+pub mod quad;
+pub mod random;
+pub mod raycast;
+pub mod rect;
 pub mod region;
+#[cfg(feature = "alloc")]
+pub mod rle;
 pub mod row;
+#[cfg(feature = "alloc")]
+pub mod sparse;
+#[cfg(feature = "alloc")]
+pub mod summed_area;
+#[cfg(feature = "alloc")]
+pub mod voronoi;
+pub mod walk;
+pub use rect::Rect;
 pub use region::{MapRegion, MapRegionMut};
 
 /// An abstraction over a 2D array of tiles.
@@ -79,7 +148,7 @@ pub trait Map {
         y: usize,
         width: usize,
         height: usize,
-    ) -> Option<MapRegion<Self::Tile, Self>>
+    ) -> Option<MapRegion<'_, Self::Tile, Self>>
     where
         Self: Sized,
     {
@@ -165,6 +234,25 @@ pub trait MapMut: Map {
         }
     }
 
+    /// Apply a [`Patch`][diff::Patch], as returned by [`diff::diff()`], to this map.
+    ///
+    /// Returns [`false`], without changing anything, if the map isn't the size the patch was
+    /// created from. Maps that can't be resized also return [`false`] if the patch changes the
+    /// size of the map, but resizable maps, like [`DynamicMap`][row::DynamicMap], resize to fit.
+    #[cfg(feature = "alloc")]
+    fn apply_patch(&mut self, patch: &diff::Patch<Self::Tile>) -> bool
+    where
+        Self::Tile: Clone,
+    {
+        if self.size() != patch.from_size() || self.size() != patch.to_size() {
+            return false;
+        }
+        for change in patch.changes() {
+            self.set(change.x, change.y, change.tile.clone());
+        }
+        true
+    }
+
     /// Get a mutable reference to a region of this map.
     ///
     /// The returned [`MapRegionMut`] also implements [`Map`] and `MapMut`, with its own coordinate
@@ -175,7 +263,7 @@ pub trait MapMut: Map {
         y: usize,
         width: usize,
         height: usize,
-    ) -> Option<MapRegionMut<Self::Tile, Self>>
+    ) -> Option<MapRegionMut<'_, Self::Tile, Self>>
     where
         Self: Sized,
     {
@@ -0,0 +1,363 @@
+//! Spreading light from point sources across a [`Map`].
+//!
+//! Light spreads from each [`LightSource`] to neighbouring tiles horizontally and vertically,
+//! growing dimmer by 1 level for every tile it enters, plus that tile's *opacity*. A tile with an
+//! opacity of [`u8::MAX`] blocks light completely. Where light from several sources overlaps, the
+//! brightest wins.
+//!
+//! Light levels can be scalar ([`u8`]), or coloured (`[u8; 3]`), where each channel spreads
+//! independently. See the [`Light`] trait.
+//!
+//! A [`LightMap`] keeps track of its sources, so when a source moves or a tile changes, only the
+//! area the change could affect is recomputed.
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::{row::DynamicMap, Map, MapMut};
+
+/// A level of light that can be spread across a map.
+pub trait Light: Copy + Default + PartialEq {
+    /// Dim the light by `amount` levels, stopping at darkness.
+    fn attenuate(self, amount: u8) -> Self;
+    /// Combine two lights, keeping the brightest of each.
+    fn brightest(self, other: Self) -> Self;
+    /// The level of the brightest part of the light. This is how far it can spread.
+    fn level(self) -> u8;
+}
+
+impl Light for u8 {
+    fn attenuate(self, amount: u8) -> Self {
+        self.saturating_sub(amount)
+    }
+
+    fn brightest(self, other: Self) -> Self {
+        self.max(other)
+    }
+
+    fn level(self) -> u8 {
+        self
+    }
+}
+
+impl Light for [u8; 3] {
+    fn attenuate(self, amount: u8) -> Self {
+        self.map(|channel| channel.saturating_sub(amount))
+    }
+
+    fn brightest(self, other: Self) -> Self {
+        [
+            self[0].max(other[0]),
+            self[1].max(other[1]),
+            self[2].max(other[2]),
+        ]
+    }
+
+    fn level(self) -> u8 {
+        self[0].max(self[1]).max(self[2])
+    }
+}
+
+/// A point that emits light.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LightSource<L> {
+    /// The x coordinate of the tile containing the source.
+    pub x: usize,
+    /// The y coordinate of the tile containing the source.
+    pub y: usize,
+    /// The light emitted. This is the light level of the source's own tile.
+    pub light: L,
+}
+
+/// The light levels across a map, lit by a set of [`LightSource`]s.
+///
+/// Each method that changes the lighting takes the map being lit, and a function returning the
+/// opacity of each tile. These should be the same every time, apart from the changes you're
+/// telling the `LightMap` about. The map should be the same size as the `LightMap`.
+///
+/// `LightMap` implements [`Map`], so the light level of each tile can be read with
+/// [`Map::get()`].
+#[derive(Clone)]
+pub struct LightMap<L> {
+    levels: DynamicMap<L>,
+    sources: Vec<Option<LightSource<L>>>,
+}
+
+impl<L: Light> LightMap<L> {
+    /// Create a new, completely dark `LightMap`, with no sources.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            levels: DynamicMap::new(width, height),
+            sources: Vec::new(),
+        }
+    }
+
+    /// Get the light levels of each tile.
+    pub fn levels(&self) -> &DynamicMap<L> {
+        &self.levels
+    }
+
+    /// Get the source with the given ID, or [`None`] if it was removed.
+    pub fn source(&self, id: usize) -> Option<&LightSource<L>> {
+        self.sources.get(id)?.as_ref()
+    }
+
+    /// Add a light source, and spread its light. Returns an ID that can be used to move or remove
+    /// the source later.
+    /// # Example
+    /// ```
+    /// use tile_maps::{lighting::{LightMap, LightSource}, row::StaticMap, prelude::*};
+    ///
+    /// let map = StaticMap::from([
+    ///     ['.', '.', '#', '.'],
+    ///     ['.', '.', '#', '.'],
+    ///     ['.', '.', '.', '.'],
+    /// ]);
+    /// let opacity = |&tile: &char| if tile == '#' { u8::MAX } else { 0 };
+    ///
+    /// let mut light = LightMap::new(4, 3);
+    /// light.add_source(&map, LightSource { x: 0, y: 0, light: 15u8 }, opacity);
+    /// assert_eq!(light.get(0, 0), Some(15));
+    /// assert_eq!(light.get(1, 1), Some(13));
+    /// assert_eq!(light.get(2, 0), Some(0));
+    /// // Light goes around the wall
+    /// assert_eq!(light.get(3, 0), Some(15 - 7));
+    /// ```
+    pub fn add_source<M, F>(&mut self, map: &M, source: LightSource<L>, mut opacity: F) -> usize
+    where
+        M: Map,
+        F: FnMut(&M::Tile) -> u8,
+    {
+        let id = self.sources.len();
+        self.sources.push(Some(source));
+        let mut queue = VecDeque::new();
+        emit(&mut self.levels, &source, &mut queue);
+        self.spread(map, &mut opacity, &mut queue, None);
+        id
+    }
+
+    /// Remove a light source, and recompute the area it lit. Returns the removed source, or
+    /// [`None`] if there's no source with that ID.
+    pub fn remove_source<M, F>(
+        &mut self,
+        map: &M,
+        id: usize,
+        mut opacity: F,
+    ) -> Option<LightSource<L>>
+    where
+        M: Map,
+        F: FnMut(&M::Tile) -> u8,
+    {
+        let source = self.sources.get_mut(id)?.take()?;
+        self.relight(map, &mut opacity, source.x, source.y, source.light.level());
+        Some(source)
+    }
+
+    /// Move a light source to a new tile. Returns [`false`] if there's no source with that ID.
+    /// # Example
+    /// ```
+    /// use tile_maps::{lighting::{LightMap, LightSource}, row::DynamicMap, prelude::*};
+    ///
+    /// let map = DynamicMap::<u8>::new(20, 1);
+    /// let mut light = LightMap::new(20, 1);
+    /// let torch = light.add_source(&map, LightSource { x: 0, y: 0, light: 4u8 }, |&o| o);
+    /// assert_eq!(light.get(2, 0), Some(2));
+    ///
+    /// light.move_source(&map, torch, 10, 0, |&o| o);
+    /// assert_eq!(light.get(2, 0), Some(0));
+    /// assert_eq!(light.get(10, 0), Some(4));
+    /// ```
+    pub fn move_source<M, F>(
+        &mut self,
+        map: &M,
+        id: usize,
+        x: usize,
+        y: usize,
+        mut opacity: F,
+    ) -> bool
+    where
+        M: Map,
+        F: FnMut(&M::Tile) -> u8,
+    {
+        let Some(old) = self.sources.get_mut(id).and_then(Option::take) else {
+            return false;
+        };
+        // Relight without the source, so its light isn't cut off at the edge of the old area
+        self.relight(map, &mut opacity, old.x, old.y, old.light.level());
+        let new = LightSource { x, y, ..old };
+        self.sources[id] = Some(new);
+        let mut queue = VecDeque::new();
+        emit(&mut self.levels, &new, &mut queue);
+        self.spread(map, &mut opacity, &mut queue, None);
+        true
+    }
+
+    /// Recompute the lighting around a tile after its opacity changed.
+    /// # Example
+    /// ```
+    /// use tile_maps::{lighting::{LightMap, LightSource}, row::DynamicMap, prelude::*};
+    ///
+    /// let mut map = DynamicMap::<u8>::new(5, 1);
+    /// let mut light = LightMap::new(5, 1);
+    /// light.add_source(&map, LightSource { x: 0, y: 0, light: [8, 4, 0] }, |&o| o);
+    /// assert_eq!(light.get(3, 0), Some([5, 1, 0]));
+    ///
+    /// // Close a door
+    /// map.set(2, 0, u8::MAX);
+    /// light.tile_changed(&map, 2, 0, |&o| o);
+    /// assert_eq!(light.get(1, 0), Some([7, 3, 0]));
+    /// assert_eq!(light.get(3, 0), Some([0, 0, 0]));
+    /// ```
+    pub fn tile_changed<M, F>(&mut self, map: &M, x: usize, y: usize, mut opacity: F)
+    where
+        M: Map,
+        F: FnMut(&M::Tile) -> u8,
+    {
+        let radius = self
+            .sources
+            .iter()
+            .flatten()
+            .map(|s| s.light.level())
+            .max()
+            .unwrap_or(0);
+        self.relight(map, &mut opacity, x, y, radius);
+    }
+
+    /// Recompute the lighting of the whole map from scratch.
+    pub fn recompute<M, F>(&mut self, map: &M, mut opacity: F)
+    where
+        M: Map,
+        F: FnMut(&M::Tile) -> u8,
+    {
+        self.levels.clear();
+        let mut queue = VecDeque::new();
+        for source in self.sources.iter().flatten() {
+            emit(&mut self.levels, source, &mut queue);
+        }
+        self.spread(map, &mut opacity, &mut queue, None);
+    }
+
+    /// Darken the tiles within `radius` of `(x, y)`, then relight them from the sources inside
+    /// the area, and the light shining in from around its edges.
+    fn relight<M, F>(&mut self, map: &M, opacity: &mut F, x: usize, y: usize, radius: u8)
+    where
+        M: Map,
+        F: FnMut(&M::Tile) -> u8,
+    {
+        let (width, height) = self.levels.size();
+        let radius = radius as usize;
+        let left = x.saturating_sub(radius);
+        let top = y.saturating_sub(radius);
+        let right = x.saturating_add(radius).min(width.saturating_sub(1));
+        let bottom = y.saturating_add(radius).min(height.saturating_sub(1));
+        let area = (left, top, right, bottom);
+
+        for y in top..=bottom {
+            for x in left..=right {
+                self.levels.set(x, y, L::default());
+            }
+        }
+
+        let mut queue = VecDeque::new();
+        // The edges of the area are unchanged, and shine into it
+        for y in top.saturating_sub(1)..=bottom.saturating_add(1).min(height.saturating_sub(1)) {
+            for x in left.saturating_sub(1)..=right.saturating_add(1).min(width.saturating_sub(1)) {
+                let inside = (left..=right).contains(&x) && (top..=bottom).contains(&y);
+                if !inside {
+                    queue.push_back((x, y));
+                }
+            }
+        }
+        let sources = self.sources.iter().flatten().copied().collect::<Vec<_>>();
+        for source in sources {
+            if (left..=right).contains(&source.x) && (top..=bottom).contains(&source.y) {
+                emit(&mut self.levels, &source, &mut queue);
+            }
+        }
+        self.spread(map, opacity, &mut queue, Some(area));
+    }
+
+    /// Spread light from the queued tiles to their neighbours, until it stops changing. If
+    /// `area` is given as `(left, top, right, bottom)`, only tiles inside it are changed.
+    fn spread<M, F>(
+        &mut self,
+        map: &M,
+        opacity: &mut F,
+        queue: &mut VecDeque<(usize, usize)>,
+        area: Option<(usize, usize, usize, usize)>,
+    ) where
+        M: Map,
+        F: FnMut(&M::Tile) -> u8,
+    {
+        let in_area = |x: usize, y: usize| {
+            area.is_none_or(|(left, top, right, bottom)| {
+                (left..=right).contains(&x) && (top..=bottom).contains(&y)
+            })
+        };
+        while let Some((x, y)) = queue.pop_front() {
+            let light = self.levels.get(x, y).expect("Coordinates are in bounds");
+            if light.level() <= 1 {
+                continue;
+            }
+            let neighbours = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbours {
+                if !in_area(nx, ny) {
+                    continue;
+                }
+                let Some(tile) = map.get_ref(nx, ny) else {
+                    continue;
+                };
+                let Some(level) = self.levels.get_mut(nx, ny) else {
+                    continue;
+                };
+                let lit = level.brightest(light.attenuate(opacity(tile).saturating_add(1)));
+                if lit != *level {
+                    *level = lit;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+    }
+}
+
+/// Light a source's own tile, and queue it to spread its light.
+fn emit<L: Light>(
+    levels: &mut DynamicMap<L>,
+    source: &LightSource<L>,
+    queue: &mut VecDeque<(usize, usize)>,
+) {
+    if let Some(level) = levels.get_mut(source.x, source.y) {
+        let lit = level.brightest(source.light);
+        if lit != *level {
+            *level = lit;
+            queue.push_back((source.x, source.y));
+        }
+    }
+}
+
+impl<L: Light> Map for LightMap<L> {
+    type Tile = L;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.levels.get(x, y)
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        self.levels.get_ref(x, y)
+    }
+
+    fn width(&self) -> usize {
+        self.levels.width()
+    }
+
+    fn height(&self) -> usize {
+        self.levels.height()
+    }
+}
@@ -0,0 +1,163 @@
+//! Morphological operations on [`Map`]s.
+//!
+//! Each operation reads tiles from a source map, selects the *foreground* tiles with a
+//! predicate, and writes the result into a [`MapMut`] of [`bool`]s. For maps of [`bool`]s, use a
+//! predicate like `|&tile| tile`.
+//!
+//! The shape of each operation is given by a *structuring element*: a list of `(x, y)` offsets
+//! relative to the tile being computed. [`CROSS`] and [`SQUARE`] cover the common cases, but any
+//! set of offsets can be used. Offsets that land outside the source map are ignored.
+//!
+//! The destination map should be at least as large as the source map. Tiles that are out of
+//! bounds of the destination are skipped.
+
+#[cfg(feature = "alloc")]
+use crate::row::DynamicMap;
+use crate::{Map, MapMut};
+
+/// A structuring element covering a tile and its 4 orthogonal neighbours.
+pub const CROSS: [(isize, isize); 5] = [(0, -1), (-1, 0), (0, 0), (1, 0), (0, 1)];
+
+/// A structuring element covering a tile and all 8 of its neighbours.
+pub const SQUARE: [(isize, isize); 9] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (0, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Erode the foreground of `src` into `dst`.
+///
+/// A tile is set in `dst` if every tile covered by `element`, centred on it, is foreground.
+/// # Example
+/// ```
+/// use tile_maps::{morphology, row::StaticMap, prelude::*};
+///
+/// let mut floor = StaticMap::<bool, 5, 5>::new();
+/// floor.region_mut(1, 1, 3, 3).unwrap().clear_to(true);
+///
+/// let mut eroded = StaticMap::<bool, 5, 5>::new();
+/// morphology::erode(&floor, &mut eroded, &morphology::SQUARE, |&tile| tile);
+/// assert_eq!(eroded.get(2, 2), Some(true));
+/// assert_eq!(eroded.get(1, 1), Some(false));
+/// ```
+pub fn erode<M, D, F>(src: &M, dst: &mut D, element: &[(isize, isize)], mut is_foreground: F)
+where
+    M: Map,
+    D: MapMut<Tile = bool>,
+    F: FnMut(&M::Tile) -> bool,
+{
+    for y in 0..src.height() {
+        for x in 0..src.width() {
+            let set = element
+                .iter()
+                .all(|&(dx, dy)| offset(src, x, y, dx, dy).is_none_or(&mut is_foreground));
+            dst.set(x, y, set);
+        }
+    }
+}
+
+/// Dilate the foreground of `src` into `dst`.
+///
+/// A tile is set in `dst` if any tile covered by the reflection of `element`, centred on it, is
+/// foreground.
+/// # Example
+/// ```
+/// use tile_maps::{morphology, row::StaticMap, prelude::*};
+///
+/// let mut walls = StaticMap::<bool, 5, 5>::new();
+/// walls.set(2, 2, true);
+///
+/// let mut dilated = StaticMap::<bool, 5, 5>::new();
+/// morphology::dilate(&walls, &mut dilated, &morphology::CROSS, |&tile| tile);
+/// assert_eq!(dilated.get(2, 1), Some(true));
+/// assert_eq!(dilated.get(3, 2), Some(true));
+/// assert_eq!(dilated.get(3, 3), Some(false));
+/// ```
+pub fn dilate<M, D, F>(src: &M, dst: &mut D, element: &[(isize, isize)], mut is_foreground: F)
+where
+    M: Map,
+    D: MapMut<Tile = bool>,
+    F: FnMut(&M::Tile) -> bool,
+{
+    for y in 0..src.height() {
+        for x in 0..src.width() {
+            let set = element
+                .iter()
+                .any(|&(dx, dy)| offset(src, x, y, -dx, -dy).is_some_and(&mut is_foreground));
+            dst.set(x, y, set);
+        }
+    }
+}
+
+/// Open the foreground of `src` into `dst`: an erosion followed by a dilation.
+///
+/// Opening removes foreground features smaller than `element`, like thin spurs and isolated
+/// tiles, while leaving larger areas intact.
+/// # Example
+/// ```
+/// use tile_maps::{morphology, row::StaticMap, prelude::*};
+///
+/// let mut floor = StaticMap::<bool, 7, 5>::new();
+/// floor.region_mut(0, 1, 3, 3).unwrap().clear_to(true);
+/// // A single-tile spur sticking out of the room
+/// floor.set(3, 2, true);
+/// floor.set(4, 2, true);
+///
+/// let mut opened = StaticMap::<bool, 7, 5>::new();
+/// morphology::open(&floor, &mut opened, &morphology::SQUARE, |&tile| tile);
+/// assert_eq!(opened.get(1, 2), Some(true));
+/// assert_eq!(opened.get(3, 2), Some(false));
+/// assert_eq!(opened.get(4, 2), Some(false));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn open<M, D, F>(src: &M, dst: &mut D, element: &[(isize, isize)], is_foreground: F)
+where
+    M: Map,
+    D: MapMut<Tile = bool>,
+    F: FnMut(&M::Tile) -> bool,
+{
+    let mut tmp = DynamicMap::new(src.width(), src.height());
+    erode(src, &mut tmp, element, is_foreground);
+    dilate(&tmp, dst, element, |&tile| tile);
+}
+
+/// Close the foreground of `src` into `dst`: a dilation followed by an erosion.
+///
+/// Closing fills background gaps smaller than `element`, like single-tile holes and narrow
+/// cracks, while leaving larger areas intact.
+/// # Example
+/// ```
+/// use tile_maps::{morphology, row::StaticMap, prelude::*};
+///
+/// let mut floor = StaticMap::<bool, 5, 5>::new();
+/// floor.clear_to(true);
+/// floor.set(2, 2, false);
+///
+/// let mut closed = StaticMap::<bool, 5, 5>::new();
+/// morphology::close(&floor, &mut closed, &morphology::CROSS, |&tile| tile);
+/// assert_eq!(closed.get(2, 2), Some(true));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn close<M, D, F>(src: &M, dst: &mut D, element: &[(isize, isize)], is_foreground: F)
+where
+    M: Map,
+    D: MapMut<Tile = bool>,
+    F: FnMut(&M::Tile) -> bool,
+{
+    let mut tmp = DynamicMap::new(src.width(), src.height());
+    dilate(src, &mut tmp, element, is_foreground);
+    erode(&tmp, dst, element, |&tile| tile);
+}
+
+/// Get the tile at `(x + dx, y + dy)`, or [`None`] if it is out of bounds.
+fn offset<M: Map>(map: &M, x: usize, y: usize, dx: isize, dy: isize) -> Option<&M::Tile> {
+    let x = x.checked_add_signed(dx)?;
+    let y = y.checked_add_signed(dy)?;
+    map.get_ref(x, y)
+}
@@ -0,0 +1,233 @@
+//! Seedable noise generators, for filling [`Map`][crate::Map]s with natural-looking terrain.
+//!
+//! [`Perlin`] gradient noise and [`Value`] noise can each be layered into several octaves of
+//! detail with [`Fractal`], then turned into tiles with [`fill()`].
+
+use crate::{random::Rng, MapMut};
+
+/// A 2D noise function.
+pub trait Noise {
+    /// Sample the noise at the point `(x, y)`. Values are roughly between -1 and 1.
+    fn sample(&self, x: f32, y: f32) -> f32;
+}
+
+impl<N: Noise + ?Sized> Noise for &N {
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        (**self).sample(x, y)
+    }
+}
+
+/// A shuffled permutation of 0 to 255, used to hash lattice points.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Permutation([u8; 256]);
+
+impl Permutation {
+    fn new(seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let mut perm = [0; 256];
+        for (i, p) in perm.iter_mut().enumerate() {
+            *p = i as u8;
+        }
+        for i in (1..perm.len()).rev() {
+            perm.swap(i, rng.below(i + 1));
+        }
+        Self(perm)
+    }
+
+    fn hash(&self, x: i32, y: i32) -> u8 {
+        let px = self.0[(x & 255) as usize];
+        self.0[(px as usize + (y & 255) as usize) & 255]
+    }
+}
+
+/// Perlin gradient noise. The noise is 0 at every integer point, and repeats every 256 units.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Perlin {
+    perm: Permutation,
+}
+
+impl Perlin {
+    /// Create a new Perlin noise generator from a seed.
+    /// # Example
+    /// ```
+    /// use tile_maps::noise::{Noise, Perlin};
+    ///
+    /// let noise = Perlin::new(42);
+    /// assert_eq!(noise.sample(3.0, 7.0), 0.0);
+    ///
+    /// let value = noise.sample(3.4, 7.8);
+    /// assert!((-1.0..=1.0).contains(&value));
+    /// assert_eq!(value, Perlin::new(42).sample(3.4, 7.8));
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        Self {
+            perm: Permutation::new(seed),
+        }
+    }
+}
+
+impl Noise for Perlin {
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let (x0, y0) = (libm::floorf(x), libm::floorf(y));
+        let (xi, yi) = (x0 as i32, y0 as i32);
+        let (xf, yf) = (x - x0, y - y0);
+        let grad = |dx: i32, dy: i32| {
+            let (gx, gy) = match self.perm.hash(xi + dx, yi + dy) & 7 {
+                0 => (1.0, 1.0),
+                1 => (-1.0, 1.0),
+                2 => (1.0, -1.0),
+                3 => (-1.0, -1.0),
+                4 => (1.0, 0.0),
+                5 => (-1.0, 0.0),
+                6 => (0.0, 1.0),
+                _ => (0.0, -1.0),
+            };
+            gx * (xf - dx as f32) + gy * (yf - dy as f32)
+        };
+        let (u, v) = (fade(xf), fade(yf));
+        lerp(
+            lerp(grad(0, 0), grad(1, 0), u),
+            lerp(grad(0, 1), grad(1, 1), u),
+            v,
+        )
+    }
+}
+
+/// Value noise, which smoothly interpolates random values at each integer point. It's cheaper
+/// than [`Perlin`] noise, but blockier. The noise repeats every 256 units.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Value {
+    perm: Permutation,
+}
+
+impl Value {
+    /// Create a new value noise generator from a seed.
+    /// # Example
+    /// ```
+    /// use tile_maps::noise::{Noise, Value};
+    ///
+    /// let noise = Value::new(42);
+    /// let value = noise.sample(3.4, 7.8);
+    /// assert!((-1.0..=1.0).contains(&value));
+    /// assert_ne!(value, Value::new(43).sample(3.4, 7.8));
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        Self {
+            perm: Permutation::new(seed),
+        }
+    }
+}
+
+impl Noise for Value {
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let (x0, y0) = (libm::floorf(x), libm::floorf(y));
+        let (xi, yi) = (x0 as i32, y0 as i32);
+        let value = |dx: i32, dy: i32| self.perm.hash(xi + dx, yi + dy) as f32 / 127.5 - 1.0;
+        let (u, v) = (fade(x - x0), fade(y - y0));
+        lerp(
+            lerp(value(0, 0), value(1, 0), u),
+            lerp(value(0, 1), value(1, 1), u),
+            v,
+        )
+    }
+}
+
+/// Several octaves of another noise function layered together, adding finer and finer detail.
+///
+/// Each octave samples the noise at `lacunarity` times the frequency, and `persistence` times the
+/// amplitude, of the one before. The sum is scaled back to the range of a single octave. Each
+/// octave is also offset, so integer points don't line up between octaves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fractal<N> {
+    /// The noise to layer.
+    pub noise: N,
+    /// The number of octaves.
+    pub octaves: u32,
+    /// How much the frequency is multiplied by for each octave.
+    pub lacunarity: f32,
+    /// How much the amplitude is multiplied by for each octave.
+    pub persistence: f32,
+}
+
+impl<N> Fractal<N> {
+    /// Layer `octaves` octaves of `noise`, doubling the frequency and halving the amplitude each
+    /// octave.
+    /// # Example
+    /// ```
+    /// use tile_maps::noise::{Fractal, Noise, Perlin};
+    ///
+    /// let noise = Fractal::new(Perlin::new(42), 4);
+    /// let value = noise.sample(3.4, 7.8);
+    /// assert!((-1.0..=1.0).contains(&value));
+    /// ```
+    pub fn new(noise: N, octaves: u32) -> Self {
+        Self {
+            noise,
+            octaves,
+            lacunarity: 2.0,
+            persistence: 0.5,
+        }
+    }
+}
+
+impl<N: Noise> Noise for Fractal<N> {
+    fn sample(&self, x: f32, y: f32) -> f32 {
+        let (mut sum, mut total) = (0.0, 0.0);
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+        for octave in 0..self.octaves {
+            let offset = octave as f32 * 17.31;
+            sum += self
+                .noise
+                .sample(x * frequency + offset, y * frequency + offset)
+                * amplitude;
+            total += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.persistence;
+        }
+        if total == 0.0 {
+            0.0
+        } else {
+            sum / total
+        }
+    }
+}
+
+/// Fill `map` with tiles generated from `noise`.
+///
+/// Each tile is sampled at its coordinates multiplied by `scale`, so smaller scales give larger
+/// features. `f` maps each noise value to a tile.
+/// # Example
+/// ```
+/// use tile_maps::{noise::{self, Fractal, Perlin}, row::StaticMap, prelude::*};
+///
+/// let mut map = StaticMap::<char, 16, 16>::new();
+/// let terrain = Fractal::new(Perlin::new(1234), 3);
+/// noise::fill(&mut map, &terrain, 0.1, |value| match value {
+///     v if v < -0.2 => '~',
+///     v if v < 0.3 => '.',
+///     _ => '^',
+/// });
+/// assert!((0..16).all(|y| (0..16).all(|x| "~.^".contains(map.get(x, y).unwrap()))));
+/// ```
+pub fn fill<M, N, F>(map: &mut M, noise: &N, scale: f32, mut f: F)
+where
+    M: MapMut,
+    N: Noise + ?Sized,
+    F: FnMut(f32) -> M::Tile,
+{
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let value = noise.sample(x as f32 * scale, y as f32 * scale);
+            map.set(x, y, f(value));
+        }
+    }
+}
+
+/// Quintic smoothing curve, so the noise changes smoothly across lattice cells.
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
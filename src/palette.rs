@@ -0,0 +1,331 @@
+//! Maps that store each distinct tile once, in a palette.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{Map, MapMut};
+
+/// The number of bits in each word of packed indices.
+const BITS: u32 = u64::BITS;
+
+/// A [`Map`] that stores a palette of distinct tiles, and an index into the palette for each
+/// tile.
+///
+/// Indices are packed into as few bits as the palette needs, growing as new tiles are added. For
+/// maps of large tiles with few distinct values, this uses far less memory than a
+/// [`DynamicMap`][crate::row::DynamicMap].
+///
+/// Each palette entry counts the tiles using it. Getting a mutable reference to a tile with
+/// [`MapMut::get_mut()`] gives access to its entry directly if no other tile uses it, or copies
+/// it to an unused entry otherwise. If the tile then matches another entry, it's moved to that
+/// entry on the next write to the map. [`PaletteMap::compact()`] removes unused entries, and
+/// shrinks the indices to match.
+#[derive(Clone, Debug)]
+pub struct PaletteMap<T> {
+    palette: Vec<T>,
+    /// The number of tiles using each palette entry.
+    counts: Vec<usize>,
+    indices: Vec<u64>,
+    bits: u32,
+    /// The tile last borrowed with `get_mut`, which may now duplicate another entry.
+    pending: Option<usize>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> PaletteMap<T> {
+    /// Create a new `PaletteMap`. Each tile will be initialised to the default tile.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{palette::PaletteMap, prelude::*};
+    /// let mut map = PaletteMap::<String>::new(100, 100);
+    /// map.set(5, 5, "Grass".to_string());
+    /// map.set(6, 5, "Grass".to_string());
+    /// map.set(7, 5, "Water".to_string());
+    ///
+    /// assert_eq!(map.get_ref(6, 5).unwrap(), "Grass");
+    /// assert_eq!(map.palette(), ["", "Grass", "Water"]);
+    /// assert_eq!(map.bits(), 2);
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: Default,
+    {
+        Self {
+            palette: vec![T::default()],
+            counts: vec![width * height],
+            indices: vec![0; words(width * height, 1)],
+            bits: 1,
+            pending: None,
+            width,
+            height,
+        }
+    }
+
+    /// Get the palette of tiles. Entries may be unused, until the map is
+    /// [compacted][PaletteMap::compact()].
+    /// # Example
+    /// ```
+    /// # use tile_maps::{palette::PaletteMap, prelude::*};
+    /// let mut map = PaletteMap::<u8>::new(100, 100);
+    /// // Regions write through `get_mut`, which reuses palette entries
+    /// map.region_mut(0, 0, 100, 100).unwrap().clear_to(7);
+    /// assert!(map.palette().len() <= 3);
+    /// assert_eq!(map.get(50, 50), Some(7));
+    ///
+    /// map.compact();
+    /// assert_eq!(map.palette(), [7]);
+    /// ```
+    pub fn palette(&self) -> &[T] {
+        &self.palette
+    }
+
+    /// Get the number of bits used to store each index into the palette.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Remove unused and duplicate entries from the palette, and shrink the indices to match.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{palette::PaletteMap, prelude::*};
+    /// let mut map = PaletteMap::<u32>::new(10, 10);
+    /// for x in 0..10 {
+    ///     map.set(x, 0, x as u32);
+    /// }
+    /// assert_eq!(map.palette().len(), 10);
+    /// assert_eq!(map.bits(), 4);
+    ///
+    /// for x in 0..5 {
+    ///     map.set(x, 0, 0);
+    /// }
+    /// map.compact();
+    /// assert_eq!(map.palette(), [0, 5, 6, 7, 8, 9]);
+    /// assert_eq!(map.bits(), 3);
+    /// ```
+    pub fn compact(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.settle();
+        let len = self.width * self.height;
+        // Map each old entry to its first equal, used entry
+        let mut remap = vec![usize::MAX; self.palette.len()];
+        let mut kept: Vec<usize> = Vec::new();
+        for (i, tile) in self.palette.iter().enumerate() {
+            if self.counts[i] == 0 {
+                continue;
+            }
+            match kept.iter().position(|&k| self.palette[k] == *tile) {
+                Some(new) => remap[i] = new,
+                None => {
+                    remap[i] = kept.len();
+                    kept.push(i);
+                }
+            }
+        }
+        if kept.len() == self.palette.len() {
+            return;
+        }
+
+        let old = core::mem::take(&mut self.palette);
+        let mut old = old.into_iter().map(Some).collect::<Vec<_>>();
+        self.palette = kept
+            .iter()
+            .map(|&i| old[i].take().expect("Each entry is kept once"))
+            .collect();
+        if self.palette.is_empty() {
+            // An empty map still needs a palette entry to grow from
+            self.palette.extend(old.into_iter().flatten().take(1));
+        }
+        let indices = (0..len).map(|i| remap[self.index(i)]).collect::<Vec<_>>();
+        self.counts = vec![0; self.palette.len()];
+        for &index in &indices {
+            self.counts[index] += 1;
+        }
+        self.repack(bits_for(self.palette.len()), indices);
+    }
+
+    /// If the tile last borrowed with `get_mut` now matches another palette entry, move it to
+    /// that entry, so its own entry can be reused.
+    fn settle(&mut self)
+    where
+        T: PartialEq,
+    {
+        let Some(i) = self.pending.take() else {
+            return;
+        };
+        let entry = self.index(i);
+        let tile = &self.palette[entry];
+        let same = self
+            .palette
+            .iter()
+            .enumerate()
+            .position(|(j, other)| j != entry && self.counts[j] > 0 && other == tile);
+        if let Some(same) = same {
+            self.assign(i, same);
+        }
+    }
+
+    /// Point the `i`th tile at a palette entry, updating the counts.
+    fn assign(&mut self, i: usize, index: usize) {
+        let old = self.index(i);
+        self.counts[old] -= 1;
+        self.counts[index] += 1;
+        self.set_index(i, index);
+    }
+
+    /// Get the palette index of the `i`th tile.
+    fn index(&self, i: usize) -> usize {
+        let per_word = (BITS / self.bits) as usize;
+        let shift = (i % per_word) as u32 * self.bits;
+        ((self.indices[i / per_word] >> shift) & mask(self.bits)) as usize
+    }
+
+    /// Set the palette index of the `i`th tile.
+    fn set_index(&mut self, i: usize, index: usize) {
+        let per_word = (BITS / self.bits) as usize;
+        let shift = (i % per_word) as u32 * self.bits;
+        let word = &mut self.indices[i / per_word];
+        *word = (*word & !(mask(self.bits) << shift)) | ((index as u64) << shift);
+    }
+
+    /// Store `indices` with `bits` bits each.
+    fn repack(&mut self, bits: u32, indices: Vec<usize>) {
+        self.bits = bits;
+        self.indices = vec![0; words(indices.len(), bits)];
+        for (i, index) in indices.into_iter().enumerate() {
+            self.set_index(i, index);
+        }
+    }
+
+    /// Add a tile to an unused palette entry, or the end of the palette, widening the indices if
+    /// needed, and return its index. The entry starts with no tiles using it.
+    fn insert(&mut self, tile: T) -> usize {
+        if let Some(free) = self.counts.iter().position(|&count| count == 0) {
+            self.palette[free] = tile;
+            return free;
+        }
+        self.palette.push(tile);
+        self.counts.push(0);
+        let bits = bits_for(self.palette.len());
+        if bits > self.bits {
+            let indices = (0..self.width * self.height)
+                .map(|i| self.index(i))
+                .collect();
+            self.repack(bits, indices);
+        }
+        self.palette.len() - 1
+    }
+}
+
+impl<T> Map for PaletteMap<T> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        if self.in_bounds(x, y) {
+            Some(&self.palette[self.index(x + y * self.width)])
+        } else {
+            None
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<T: Clone + PartialEq> MapMut for PaletteMap<T> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Tile> {
+        if !self.in_bounds(x, y) {
+            return None;
+        }
+        self.settle();
+        let i = x + y * self.width;
+        let mut index = self.index(i);
+        if self.counts[index] > 1 {
+            // Other tiles share this entry, so give this tile its own copy
+            let tile = self.palette[index].clone();
+            let copy = self.insert(tile);
+            self.assign(i, copy);
+            index = copy;
+        }
+        self.pending = Some(i);
+        Some(&mut self.palette[index])
+    }
+
+    fn replace(&mut self, x: usize, y: usize, new: Self::Tile) -> Result<Self::Tile, Self::Tile> {
+        match self.get_ref(x, y) {
+            Some(old) => {
+                let old = old.clone();
+                self.set(x, y, new);
+                Ok(old)
+            }
+            None => Err(new),
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, new: Self::Tile) -> bool {
+        if !self.in_bounds(x, y) {
+            return false;
+        }
+        self.settle();
+        let i = x + y * self.width;
+        let found = self
+            .palette
+            .iter()
+            .enumerate()
+            .position(|(j, tile)| self.counts[j] > 0 && *tile == new);
+        let index = match found {
+            Some(index) => index,
+            None if self.counts[self.index(i)] == 1 => {
+                // This tile is the only one using its entry, so reuse it
+                let index = self.index(i);
+                self.palette[index] = new;
+                return true;
+            }
+            None => self.insert(new),
+        };
+        self.assign(i, index);
+        true
+    }
+
+    fn clear(&mut self)
+    where
+        Self::Tile: Default,
+    {
+        self.clear_to(T::default());
+    }
+
+    fn clear_to(&mut self, new: Self::Tile) {
+        self.palette = vec![new];
+        self.counts = vec![self.width * self.height];
+        self.bits = 1;
+        self.indices = vec![0; words(self.width * self.height, 1)];
+        self.pending = None;
+    }
+}
+
+/// Get the number of bits needed to index a palette of `len` entries.
+fn bits_for(len: usize) -> u32 {
+    usize::BITS - (len.max(2) - 1).leading_zeros()
+}
+
+/// Get the number of words needed to store `len` indices of `bits` bits each.
+fn words(len: usize, bits: u32) -> usize {
+    len.div_ceil((BITS / bits) as usize)
+}
+
+fn mask(bits: u32) -> u64 {
+    u64::MAX >> (BITS - bits)
+}
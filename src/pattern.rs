@@ -0,0 +1,225 @@
+//! Finding, and replacing, small patterns of tiles inside larger [`Map`]s.
+//!
+//! Patterns are themselves maps, with tiles of type `Option<T>`. A [`None`] tile is a wildcard,
+//! matching any tile. Patterns can be matched as they are, or rotated and flipped by a
+//! [`Transform`].
+
+use alloc::vec::Vec;
+
+use crate::{Map, MapMut};
+
+/// A rotation or reflection of a pattern. Rotations are clockwise, with the y axis pointing down.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Transform {
+    /// The pattern as it is.
+    #[default]
+    Identity,
+    /// Rotated a quarter turn clockwise.
+    Rotate90,
+    /// Rotated a half turn.
+    Rotate180,
+    /// Rotated three quarter turns clockwise (a quarter turn anti-clockwise).
+    Rotate270,
+    /// Mirrored left to right.
+    FlipX,
+    /// Mirrored top to bottom.
+    FlipY,
+    /// Mirrored across the diagonal from the top-left corner to the bottom-right.
+    Transpose,
+    /// Mirrored across the diagonal from the top-right corner to the bottom-left.
+    AntiTranspose,
+}
+
+impl Transform {
+    /// Just the pattern as it is.
+    pub const IDENTITY: [Self; 1] = [Self::Identity];
+    /// All 4 rotations of a pattern.
+    pub const ROTATIONS: [Self; 4] = [
+        Self::Identity,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+    ];
+    /// All 8 rotations and reflections of a pattern.
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::FlipX,
+        Self::FlipY,
+        Self::Transpose,
+        Self::AntiTranspose,
+    ];
+
+    /// Get the size (width, height) of a pattern of the given size after this transform.
+    pub fn size(self, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Self::Identity | Self::Rotate180 | Self::FlipX | Self::FlipY => (width, height),
+            Self::Rotate90 | Self::Rotate270 | Self::Transpose | Self::AntiTranspose => {
+                (height, width)
+            }
+        }
+    }
+
+    /// Map coordinates in a transformed pattern back to coordinates in the original pattern,
+    /// which is `width` by `height` tiles.
+    /// # Example
+    /// ```
+    /// use tile_maps::pattern::Transform;
+    ///
+    /// // The top-left of a pattern rotated clockwise was the bottom-left of the original
+    /// assert_eq!(Transform::Rotate90.source(0, 0, 3, 2), (0, 1));
+    /// ```
+    pub fn source(self, x: usize, y: usize, width: usize, height: usize) -> (usize, usize) {
+        match self {
+            Self::Identity => (x, y),
+            Self::Rotate90 => (y, height - 1 - x),
+            Self::Rotate180 => (width - 1 - x, height - 1 - y),
+            Self::Rotate270 => (width - 1 - y, x),
+            Self::FlipX => (width - 1 - x, y),
+            Self::FlipY => (x, height - 1 - y),
+            Self::Transpose => (y, x),
+            Self::AntiTranspose => (width - 1 - y, height - 1 - x),
+        }
+    }
+}
+
+/// Where a pattern was found, as returned by [`find()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// The x coordinate of the top-left of the transformed pattern in the map.
+    pub x: usize,
+    /// The y coordinate of the top-left of the transformed pattern in the map.
+    pub y: usize,
+    /// The transform applied to the pattern to match.
+    pub transform: Transform,
+}
+
+/// Find all the places `pattern` occurs in `map`, under any of the given `transforms`.
+///
+/// Each position is reported at most once, with the first transform (in the order given) that
+/// matched there. Matches are ordered by position, from left to right and top to bottom.
+/// # Example
+/// ```
+/// use tile_maps::{pattern::{self, Transform}, row::StaticMap};
+///
+/// let map = StaticMap::from([
+///     ['#', '#', '.', '.'],
+///     ['#', '.', '.', '#'],
+///     ['.', '.', '#', '#'],
+/// ]);
+/// // A corner of wall, with anything in the middle
+/// let corner = StaticMap::from([[Some('#'), Some('#')], [Some('#'), None]]);
+///
+/// let found = pattern::find(&map, &corner, &Transform::IDENTITY);
+/// assert_eq!(found.len(), 1);
+/// assert_eq!((found[0].x, found[0].y), (0, 0));
+///
+/// let found = pattern::find(&map, &corner, &Transform::ROTATIONS);
+/// assert_eq!(found.len(), 2);
+/// assert_eq!((found[1].x, found[1].y), (2, 1));
+/// assert_eq!(found[1].transform, Transform::Rotate180);
+/// ```
+pub fn find<T, M, P>(map: &M, pattern: &P, transforms: &[Transform]) -> Vec<Match>
+where
+    T: PartialEq,
+    M: Map<Tile = T>,
+    P: Map<Tile = Option<T>>,
+{
+    let mut matches = Vec::new();
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let transform = transforms
+                .iter()
+                .copied()
+                .find(|&transform| matches_at(map, pattern, x, y, transform));
+            if let Some(transform) = transform {
+                matches.push(Match { x, y, transform });
+            }
+        }
+    }
+    matches
+}
+
+/// Find all the places `pattern` occurs in `map`, and replace them with `replacement`.
+///
+/// The replacement is transformed the same way as the pattern was to match, and only its
+/// non-[`None`] tiles are written. All matches are found before any are replaced, so
+/// replacements don't create or destroy other matches. Where matches overlap, later ones (in the
+/// order of [`find()`]) overwrite earlier ones.
+///
+/// Returns the number of replacements made, or [`None`] if `replacement` isn't the same size as
+/// `pattern`.
+/// # Example
+/// ```
+/// use tile_maps::{pattern::{self, Transform}, row::StaticMap, prelude::*};
+///
+/// let mut map = StaticMap::from([
+///     ['#', '.', '#', '.', '.'],
+///     ['.', '.', '.', '.', '#'],
+///     ['.', '.', '.', '.', '.'],
+///     ['.', '.', '.', '.', '#'],
+/// ]);
+/// // Replace every wall-gap-wall with a pillar in the gap
+/// let gap = StaticMap::from([[Some('#'), Some('.'), Some('#')]]);
+/// let pillar = StaticMap::from([[None, Some('I'), None]]);
+///
+/// let replaced = pattern::replace(&mut map, &gap, &pillar, &Transform::ROTATIONS);
+/// assert_eq!(replaced, Some(2));
+/// assert_eq!(map.get(1, 0), Some('I'));
+/// assert_eq!(map.get(4, 2), Some('I'));
+/// ```
+pub fn replace<T, M, P, R>(
+    map: &mut M,
+    pattern: &P,
+    replacement: &R,
+    transforms: &[Transform],
+) -> Option<usize>
+where
+    T: PartialEq + Clone,
+    M: MapMut<Tile = T>,
+    P: Map<Tile = Option<T>>,
+    R: Map<Tile = Option<T>>,
+{
+    if replacement.size() != pattern.size() {
+        return None;
+    }
+    let matches = find(map, pattern, transforms);
+    let (width, height) = replacement.size();
+    for m in &matches {
+        let (t_width, t_height) = m.transform.size(width, height);
+        for y in 0..t_height {
+            for x in 0..t_width {
+                let (sx, sy) = m.transform.source(x, y, width, height);
+                if let Some(Some(tile)) = replacement.get_ref(sx, sy) {
+                    map.set(m.x + x, m.y + y, tile.clone());
+                }
+            }
+        }
+    }
+    Some(matches.len())
+}
+
+/// Test if `pattern`, transformed by `transform`, matches `map` with its top-left at `(x, y)`.
+fn matches_at<T, M, P>(map: &M, pattern: &P, x: usize, y: usize, transform: Transform) -> bool
+where
+    T: PartialEq,
+    M: Map<Tile = T>,
+    P: Map<Tile = Option<T>>,
+{
+    let (width, height) = pattern.size();
+    let (t_width, t_height) = transform.size(width, height);
+    if x + t_width > map.width() || y + t_height > map.height() {
+        return false;
+    }
+    (0..t_height).all(|py| {
+        (0..t_width).all(|px| {
+            let (sx, sy) = transform.source(px, py, width, height);
+            match pattern.get_ref(sx, sy) {
+                Some(Some(expected)) => map.get_ref(x + px, y + py) == Some(expected),
+                _ => true,
+            }
+        })
+    })
+}
@@ -0,0 +1,177 @@
+//! Immutable maps, that share storage between versions.
+
+use alloc::{sync::Arc, vec, vec::Vec};
+
+use crate::Map;
+
+/// The number of bits of a tile's index used at each level of the tree.
+const BITS: u32 = 5;
+/// The number of children of each node.
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+/// A node of a [`PersistentMap`]'s tree.
+#[derive(Debug)]
+enum Node<T> {
+    Leaf(Arc<[T]>),
+    Branch(Arc<[Node<T>]>),
+}
+
+impl<T> Clone for Node<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Leaf(tiles) => Self::Leaf(tiles.clone()),
+            Self::Branch(children) => Self::Branch(children.clone()),
+        }
+    }
+}
+
+/// An immutable [`Map`], where setting a tile returns a new map that shares most of its storage
+/// with the old one.
+///
+/// Tiles are stored in chunks, at the leaves of a tree. Setting a tile copies its chunk, and the
+/// short path of nodes leading to it, so keeping every version of a map, for undo history, is
+/// cheap. As maps are never changed, they can be shared freely, even between threads, and
+/// cloning one only copies a reference.
+#[derive(Debug)]
+pub struct PersistentMap<T> {
+    root: Node<T>,
+    /// The number of levels of branches above the leaves.
+    depth: u32,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Clone for PersistentMap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            depth: self.depth,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> PersistentMap<T> {
+    /// Create a new `PersistentMap`. Each tile will be initialised to the default tile.
+    ///
+    /// Every chunk starts out shared, so a new map only stores a single chunk, however large it
+    /// is.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{persistent::PersistentMap, prelude::*};
+    /// let map = PersistentMap::<u8>::new(100, 100);
+    /// let changed = map.set(10, 20, 1).unwrap();
+    /// assert_eq!(map.get(10, 20), Some(0));
+    /// assert_eq!(changed.get(10, 20), Some(1));
+    ///
+    /// assert!(map.set(100, 0, 1).is_none());
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: Default + Clone,
+    {
+        let len = width * height;
+        let mut root = Node::Leaf(Arc::from(vec![T::default(); WIDTH]));
+        let mut depth = 0;
+        while WIDTH.pow(depth + 1) < len {
+            root = Node::Branch(Arc::from(vec![root; WIDTH]));
+            depth += 1;
+        }
+        Self {
+            root,
+            depth,
+            width,
+            height,
+        }
+    }
+
+    /// Return a new map with the tile at `(x, y)` set to `new`, or [`None`] if the coordinates
+    /// are out of bounds.
+    ///
+    /// The new map shares all of its storage with this one, except the chunk containing the tile
+    /// and the nodes leading to it.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{persistent::PersistentMap, prelude::*};
+    /// let mut history = vec![PersistentMap::<char>::new(10, 10)];
+    /// for x in 0..5 {
+    ///     let next = history.last().unwrap().set(x, 0, '#').unwrap();
+    ///     history.push(next);
+    /// }
+    ///
+    /// // Undo twice
+    /// history.truncate(history.len() - 2);
+    /// let map = history.last().unwrap();
+    /// assert_eq!(map.get(2, 0), Some('#'));
+    /// assert_eq!(map.get(3, 0), Some('\0'));
+    /// ```
+    pub fn set(&self, x: usize, y: usize, new: T) -> Option<Self>
+    where
+        T: Clone,
+    {
+        if !self.in_bounds(x, y) {
+            return None;
+        }
+        Some(Self {
+            root: set(&self.root, x + y * self.width, BITS * self.depth, new),
+            depth: self.depth,
+            width: self.width,
+            height: self.height,
+        })
+    }
+}
+
+impl<T> Map for PersistentMap<T> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        if !self.in_bounds(x, y) {
+            return None;
+        }
+        let i = x + y * self.width;
+        let (mut node, mut shift) = (&self.root, BITS * self.depth);
+        loop {
+            match node {
+                Node::Leaf(tiles) => return Some(&tiles[i & MASK]),
+                Node::Branch(children) => {
+                    node = &children[(i >> shift) & MASK];
+                    shift -= BITS;
+                }
+            }
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+/// Copy the path from `node` to the `i`th tile, setting the tile to `new`.
+fn set<T: Clone>(node: &Node<T>, i: usize, shift: u32, new: T) -> Node<T> {
+    match node {
+        Node::Leaf(tiles) => {
+            let mut tiles = tiles.to_vec();
+            tiles[i & MASK] = new;
+            Node::Leaf(Arc::from(tiles))
+        }
+        Node::Branch(children) => {
+            let mut copy: Vec<Node<T>> = children.to_vec();
+            let child = (i >> shift) & MASK;
+            copy[child] = set(&children[child], i, shift - BITS, new);
+            Node::Branch(Arc::from(copy))
+        }
+    }
+}
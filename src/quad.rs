@@ -0,0 +1,321 @@
+//! Maps stored as region quadtrees, which collapse large areas of identical tiles.
+
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::{Map, MapMut, Rect};
+
+/// A node of a [`QuadMap`]. Children are ordered top-left, top-right, bottom-left, bottom-right.
+#[derive(Clone, Debug)]
+enum Node<T> {
+    Leaf(T),
+    Branch(Box<[Node<T>; 4]>),
+}
+
+/// A [`Map`] stored as a region quadtree.
+///
+/// The map is divided into quadrants, which are divided into quadrants, and so on, until each
+/// quadrant holds only one distinct tile. Large areas of identical tiles, like oceans, take the
+/// memory of a single tile.
+///
+/// [`MapMut::set()`] splits and merges quadrants as needed. [`MapMut::get_mut()`] has to split
+/// quadrants down to the single tile, as the tile might change, so call [`QuadMap::compact()`]
+/// afterwards to merge them back together.
+#[derive(Clone, Debug)]
+pub struct QuadMap<T> {
+    root: Node<T>,
+    width: usize,
+    height: usize,
+    /// The width and height of the root quadrant, a power of two.
+    size: usize,
+}
+
+impl<T> QuadMap<T> {
+    /// Create a new `QuadMap`. Each tile will be initialised to the default tile.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{quad::QuadMap, prelude::*};
+    /// let mut map = QuadMap::<char>::new(1000, 600);
+    /// map.clear_to('~');
+    /// map.set(100, 200, '#');
+    /// assert_eq!(map.get(100, 200), Some('#'));
+    /// assert_eq!(map.get(101, 200), Some('~'));
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: Default,
+    {
+        Self {
+            root: Node::Leaf(T::default()),
+            width,
+            height,
+            size: width.max(height).max(1).next_power_of_two(),
+        }
+    }
+
+    /// Iterate over the uniform blocks of the map, as rectangles and the tile that fills them.
+    ///
+    /// Blocks are squares, clipped to the bounds of the map, and are returned in no particular
+    /// order. Neighbouring blocks may hold the same tile.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{quad::QuadMap, Rect, prelude::*};
+    /// let mut map = QuadMap::<u8>::new(100, 100);
+    /// assert_eq!(map.blocks().collect::<Vec<_>>(), [(Rect::new(0, 0, 100, 100), &0)]);
+    ///
+    /// map.set(0, 0, 1);
+    /// assert_eq!(map.blocks().count(), 22);
+    /// assert_eq!(map.blocks().filter(|&(_, &tile)| tile == 1).count(), 1);
+    /// ```
+    pub fn blocks(&self) -> Blocks<'_, T> {
+        Blocks {
+            stack: vec![(&self.root, 0, 0, self.size)],
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Merge every quadrant whose tiles are all identical into a single tile.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{quad::QuadMap, prelude::*};
+    /// let mut map = QuadMap::<u8>::new(64, 64);
+    /// *map.get_mut(10, 10).unwrap() += 0;
+    /// assert!(map.blocks().count() > 1);
+    ///
+    /// map.compact();
+    /// assert_eq!(map.blocks().count(), 1);
+    /// ```
+    pub fn compact(&mut self)
+    where
+        T: Clone + PartialEq,
+    {
+        compact(&mut self.root, 0, 0, self.size, (self.width, self.height));
+    }
+}
+
+impl<T> Map for QuadMap<T> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        if !self.in_bounds(x, y) {
+            return None;
+        }
+        let (mut node, mut x, mut y, mut size) = (&self.root, x, y, self.size);
+        loop {
+            match node {
+                Node::Leaf(tile) => return Some(tile),
+                Node::Branch(children) => {
+                    size /= 2;
+                    node = &children[quadrant(x, y, size)];
+                    (x, y) = (x % size, y % size);
+                }
+            }
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<T: Clone + PartialEq> MapMut for QuadMap<T> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Tile> {
+        if self.in_bounds(x, y) {
+            Some(leaf_mut(&mut self.root, x, y, self.size))
+        } else {
+            None
+        }
+    }
+
+    fn replace(&mut self, x: usize, y: usize, new: Self::Tile) -> Result<Self::Tile, Self::Tile> {
+        match self.get_ref(x, y) {
+            Some(old) => {
+                let old = old.clone();
+                self.set(x, y, new);
+                Ok(old)
+            }
+            None => Err(new),
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, new: Self::Tile) -> bool {
+        if self.in_bounds(x, y) {
+            let bounds = (self.width, self.height);
+            set(&mut self.root, (0, 0), self.size, (x, y), new, bounds);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn clear(&mut self)
+    where
+        Self::Tile: Default,
+    {
+        self.root = Node::Leaf(T::default());
+    }
+
+    fn clear_to(&mut self, new: Self::Tile) {
+        self.root = Node::Leaf(new);
+    }
+}
+
+/// An iterator over the uniform blocks of a [`QuadMap`], as returned by [`QuadMap::blocks()`].
+#[derive(Clone, Debug)]
+pub struct Blocks<'a, T> {
+    /// Nodes left to visit, with their top-left corner and size.
+    stack: Vec<(&'a Node<T>, usize, usize, usize)>,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> Iterator for Blocks<'a, T> {
+    type Item = (Rect, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, x, y, size)) = self.stack.pop() {
+            // Skip quadrants that only pad the map out to a power of two
+            if x >= self.width || y >= self.height {
+                continue;
+            }
+            match node {
+                Node::Leaf(tile) => {
+                    let width = size.min(self.width - x);
+                    let height = size.min(self.height - y);
+                    return Some((Rect::new(x, y, width, height), tile));
+                }
+                Node::Branch(children) => {
+                    let half = size / 2;
+                    for (i, child) in children.iter().enumerate().rev() {
+                        self.stack
+                            .push((child, x + i % 2 * half, y + i / 2 * half, half));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Get the index of the child quadrant containing `(x, y)`, where each child is `half` wide.
+fn quadrant(x: usize, y: usize, half: usize) -> usize {
+    usize::from(x >= half) + 2 * usize::from(y >= half)
+}
+
+/// Split a leaf into four identical children.
+fn split<T: Clone>(node: &mut Node<T>) {
+    if let Node::Leaf(tile) = node {
+        let tile = tile.clone();
+        *node = Node::Branch(Box::new([
+            Node::Leaf(tile.clone()),
+            Node::Leaf(tile.clone()),
+            Node::Leaf(tile.clone()),
+            Node::Leaf(tile),
+        ]));
+    }
+}
+
+/// Get a mutable reference to a tile, splitting quadrants down to that tile.
+fn leaf_mut<T: Clone>(node: &mut Node<T>, x: usize, y: usize, size: usize) -> &mut T {
+    if size > 1 {
+        split(node);
+    }
+    match node {
+        Node::Leaf(tile) => tile,
+        Node::Branch(children) => {
+            let half = size / 2;
+            leaf_mut(
+                &mut children[quadrant(x, y, half)],
+                x % half,
+                y % half,
+                half,
+            )
+        }
+    }
+}
+
+/// Set the tile at `(x, y)`, relative to the node at `origin`, then merge the node if possible.
+fn set<T: Clone + PartialEq>(
+    node: &mut Node<T>,
+    origin: (usize, usize),
+    size: usize,
+    (x, y): (usize, usize),
+    new: T,
+    bounds: (usize, usize),
+) {
+    match node {
+        Node::Leaf(tile) if *tile == new => return,
+        _ if size == 1 => {
+            *node = Node::Leaf(new);
+            return;
+        }
+        _ => split(node),
+    }
+    if let Node::Branch(children) = node {
+        let half = size / 2;
+        let i = quadrant(x, y, half);
+        let child_origin = (origin.0 + i % 2 * half, origin.1 + i / 2 * half);
+        set(
+            &mut children[i],
+            child_origin,
+            half,
+            (x % half, y % half),
+            new,
+            bounds,
+        );
+    }
+    merge(node, origin, size, bounds);
+}
+
+/// Merge the children of a node if they're leaves holding the same tile. Children that only pad
+/// the map out to a power of two are ignored.
+fn merge<T: Clone + PartialEq>(
+    node: &mut Node<T>,
+    origin: (usize, usize),
+    size: usize,
+    (width, height): (usize, usize),
+) {
+    let Node::Branch(children) = node else {
+        return;
+    };
+    let half = size / 2;
+    // The top-left child is always in bounds, as its parent is
+    let Node::Leaf(first) = &children[0] else {
+        return;
+    };
+    let uniform = children.iter().enumerate().skip(1).all(|(i, child)| {
+        let (x, y) = (origin.0 + i % 2 * half, origin.1 + i / 2 * half);
+        x >= width || y >= height || matches!(child, Node::Leaf(tile) if tile == first)
+    });
+    if uniform {
+        *node = Node::Leaf(first.clone());
+    }
+}
+
+/// Merge every uniform quadrant under a node, from the bottom up.
+fn compact<T: Clone + PartialEq>(
+    node: &mut Node<T>,
+    x: usize,
+    y: usize,
+    size: usize,
+    bounds: (usize, usize),
+) {
+    if let Node::Branch(children) = node {
+        let half = size / 2;
+        for (i, child) in children.iter_mut().enumerate() {
+            compact(child, x + i % 2 * half, y + i / 2 * half, half, bounds);
+        }
+    }
+    merge(node, (x, y), size, bounds);
+}
@@ -0,0 +1,75 @@
+//! A small, seedable random number generator, for procedural generation.
+//!
+//! This isn't suitable for cryptography, but it is fast, doesn't allocate, and always produces
+//! the same sequence from the same seed, on every platform.
+
+/// A seedable pseudo-random number generator, using the SplitMix64 algorithm.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a new `Rng` from a seed.
+    /// # Example
+    /// ```
+    /// use tile_maps::random::Rng;
+    ///
+    /// let mut a = Rng::new(42);
+    /// let mut b = Rng::new(42);
+    /// assert_eq!(a.next_u64(), b.next_u64());
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generate a random [`u64`].
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a random [`u32`].
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Generate a random number from 0 up to, but not including, `bound`. Returns 0 if `bound`
+    /// is 0.
+    /// # Example
+    /// ```
+    /// use tile_maps::random::Rng;
+    ///
+    /// let mut rng = Rng::new(7);
+    /// for _ in 0..100 {
+    ///     assert!(rng.below(6) < 6);
+    /// }
+    /// ```
+    pub fn below(&mut self, bound: usize) -> usize {
+        if bound == 0 {
+            return 0;
+        }
+        // Reject values from the incomplete range at the top, so every result is equally likely
+        let bound = bound as u64;
+        let zone = u64::MAX - (u64::MAX - bound + 1) % bound;
+        loop {
+            let value = self.next_u64();
+            if value <= zone {
+                return (value % bound) as usize;
+            }
+        }
+    }
+
+    /// Generate a random [`f32`] from 0 up to, but not including, 1.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns [`true`] with a probability of `p`, from 0 to 1.
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.next_f32() < p
+    }
+}
@@ -0,0 +1,214 @@
+//! Casting rays through [`Map`]s, using a digital differential analyser (DDA).
+//!
+//! Rays are cast in continuous space, where the tile at `(x, y)` covers the square from `(x, y)`
+//! to `(x + 1, y + 1)`. Distances are measured in tiles along the ray, so they don't depend on the
+//! length of the direction vector.
+//!
+//! Rays visit every tile they pass through, in order, without skipping any corners. For a ray
+//! passing exactly through a corner, the tile to its side along the x axis is visited first.
+
+use crate::Map;
+
+/// A tile visited by a ray, as returned by [`traverse()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step {
+    /// The x coordinate of the tile. May be negative, or outside the bounds of a map.
+    pub x: isize,
+    /// The y coordinate of the tile. May be negative, or outside the bounds of a map.
+    pub y: isize,
+    /// The distance along the ray at which it entered the tile.
+    pub distance: f32,
+    /// The normal of the face of the tile the ray entered through, pointing back towards the
+    /// ray. `(0, 0)` for the tile containing the origin.
+    pub normal: (isize, isize),
+}
+
+/// Information about where a ray hit a tile, as returned by [`cast()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hit {
+    /// The x coordinate of the tile that was hit.
+    pub x: usize,
+    /// The y coordinate of the tile that was hit.
+    pub y: usize,
+    /// The distance along the ray at which it hit the tile.
+    pub distance: f32,
+    /// The point where the ray hit the tile.
+    pub point: (f32, f32),
+    /// The normal of the face of the tile that was hit, pointing back towards the ray. `(0, 0)`
+    /// if the origin of the ray was inside the tile.
+    pub normal: (isize, isize),
+}
+
+/// An [`Iterator`] over the tiles a ray passes through. See [`traverse()`].
+#[derive(Clone, Debug)]
+pub struct Traversal {
+    next: Option<Step>,
+    step: (isize, isize),
+    /// Distance along the ray to the next vertical and horizontal tile boundaries.
+    boundary: (f32, f32),
+    /// Distance along the ray between vertical and horizontal tile boundaries.
+    delta: (f32, f32),
+    max_distance: f32,
+}
+
+impl Iterator for Traversal {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        let (x, y, distance, normal) = if self.boundary.0 <= self.boundary.1 {
+            let distance = self.boundary.0;
+            self.boundary.0 += self.delta.0;
+            (
+                current.x + self.step.0,
+                current.y,
+                distance,
+                (-self.step.0, 0),
+            )
+        } else {
+            let distance = self.boundary.1;
+            self.boundary.1 += self.delta.1;
+            (
+                current.x,
+                current.y + self.step.1,
+                distance,
+                (0, -self.step.1),
+            )
+        };
+        self.next = (distance <= self.max_distance && distance.is_finite()).then_some(Step {
+            x,
+            y,
+            distance,
+            normal,
+        });
+        Some(current)
+    }
+}
+
+impl core::iter::FusedIterator for Traversal {}
+
+/// Get an [`Iterator`] over the tiles a ray passes through, up to `max_distance` tiles along
+/// the ray.
+///
+/// `direction` doesn't need to be normalised. If it is `(0, 0)`, only the tile containing
+/// `origin` is visited.
+/// # Example
+/// ```
+/// use tile_maps::raycast;
+///
+/// let tiles = raycast::traverse((0.5, 0.5), (1.0, 0.5), 2.5)
+///     .map(|step| (step.x, step.y))
+///     .collect::<Vec<_>>();
+/// assert_eq!(tiles, [(0, 0), (1, 0), (1, 1), (2, 1)]);
+/// ```
+pub fn traverse(origin: (f32, f32), direction: (f32, f32), max_distance: f32) -> Traversal {
+    let length = libm::sqrtf(direction.0 * direction.0 + direction.1 * direction.1);
+    let (dx, dy) = if length > 0.0 {
+        (direction.0 / length, direction.1 / length)
+    } else {
+        (0.0, 0.0)
+    };
+    let (tile_x, tile_y) = (libm::floorf(origin.0), libm::floorf(origin.1));
+    let (step_x, boundary_x, delta_x) = axis(origin.0, tile_x, dx);
+    let (step_y, boundary_y, delta_y) = axis(origin.1, tile_y, dy);
+
+    Traversal {
+        next: (max_distance >= 0.0).then_some(Step {
+            x: tile_x as isize,
+            y: tile_y as isize,
+            distance: 0.0,
+            normal: (0, 0),
+        }),
+        step: (step_x, step_y),
+        boundary: (boundary_x, boundary_y),
+        delta: (delta_x, delta_y),
+        max_distance,
+    }
+}
+
+/// Cast a ray through `map`, returning the first tile it hits that matches `predicate`.
+///
+/// Returns [`None`] if the ray travels `max_distance` tiles without hitting a matching tile.
+/// Tiles outside the bounds of the map are never hit, but the ray carries on through them, so it
+/// may start outside the map. The ray stops early once it has left the map and is heading away
+/// from it, so `max_distance` may be [`f32::INFINITY`].
+/// # Example
+/// ```
+/// use tile_maps::{raycast, row::StaticMap};
+///
+/// let map = StaticMap::from([
+///     ['.', '.', '.', '#'],
+///     ['.', '.', '.', '#'],
+///     ['.', '.', '.', '#'],
+/// ]);
+/// let hit = raycast::cast(&map, (0.5, 1.5), (1.0, 0.0), 10.0, |&tile| tile == '#').unwrap();
+/// assert_eq!((hit.x, hit.y), (3, 1));
+/// assert_eq!(hit.normal, (-1, 0));
+/// assert_eq!(hit.distance, 2.5);
+/// assert_eq!(hit.point, (3.0, 1.5));
+///
+/// // Too far away
+/// assert!(raycast::cast(&map, (0.5, 1.5), (1.0, 0.0), 2.0, |&tile| tile == '#').is_none());
+///
+/// // Misses the map entirely, without travelling forever
+/// let miss = raycast::cast(&map, (0.5, 1.5), (-1.0, 0.0), f32::INFINITY, |&tile| tile == '#');
+/// assert!(miss.is_none());
+/// ```
+pub fn cast<M, F>(
+    map: &M,
+    origin: (f32, f32),
+    direction: (f32, f32),
+    max_distance: f32,
+    mut predicate: F,
+) -> Option<Hit>
+where
+    M: Map,
+    F: FnMut(&M::Tile) -> bool,
+{
+    let length = libm::sqrtf(direction.0 * direction.0 + direction.1 * direction.1);
+    let (width, height) = (map.width() as isize, map.height() as isize);
+    for step in traverse(origin, direction, max_distance) {
+        // Once the ray is outside the map and heading away from it, it can never hit anything
+        let leaving_x =
+            (step.x < 0 && direction.0 <= 0.0) || (step.x >= width && direction.0 >= 0.0);
+        let leaving_y =
+            (step.y < 0 && direction.1 <= 0.0) || (step.y >= height && direction.1 >= 0.0);
+        if leaving_x || leaving_y {
+            return None;
+        }
+        let (Ok(x), Ok(y)) = (usize::try_from(step.x), usize::try_from(step.y)) else {
+            continue;
+        };
+        if !map.get_ref(x, y).is_some_and(&mut predicate) {
+            continue;
+        }
+        let point = if length > 0.0 {
+            (
+                origin.0 + direction.0 / length * step.distance,
+                origin.1 + direction.1 / length * step.distance,
+            )
+        } else {
+            origin
+        };
+        return Some(Hit {
+            x,
+            y,
+            distance: step.distance,
+            point,
+            normal: step.normal,
+        });
+    }
+    None
+}
+
+/// Work out the step direction, the distance to the first tile boundary, and the distance
+/// between boundaries along one axis of a ray.
+fn axis(origin: f32, tile: f32, direction: f32) -> (isize, f32, f32) {
+    if direction > 0.0 {
+        (1, (tile + 1.0 - origin) / direction, 1.0 / direction)
+    } else if direction < 0.0 {
+        (-1, (origin - tile) / -direction, 1.0 / -direction)
+    } else {
+        (0, f32::INFINITY, f32::INFINITY)
+    }
+}
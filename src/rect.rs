@@ -0,0 +1,131 @@
+//! Axis-aligned rectangles of tiles.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::Map;
+
+/// An axis-aligned rectangle of tiles, with its top-left corner at `(x, y)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    /// The x coordinate of the left of the rectangle.
+    pub x: usize,
+    /// The y coordinate of the top of the rectangle.
+    pub y: usize,
+    /// The width of the rectangle, in tiles.
+    pub width: usize,
+    /// The height of the rectangle, in tiles.
+    pub height: usize,
+}
+
+impl Rect {
+    /// Create a new `Rect`.
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the x coordinate of the right of the rectangle (exclusive).
+    pub fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    /// Returns the y coordinate of the bottom of the rectangle (exclusive).
+    pub fn bottom(&self) -> usize {
+        self.y + self.height
+    }
+
+    /// Returns the number of tiles covered by the rectangle.
+    pub fn area(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// Test if the rectangle covers the tile at `(x, y)`.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.x..self.right()).contains(&x) && (self.y..self.bottom()).contains(&y)
+    }
+
+    /// Test if the rectangle fits within the bounds of `map`.
+    pub fn in_bounds<M: Map>(&self, map: &M) -> bool {
+        self.right() <= map.width() && self.bottom() <= map.height()
+    }
+}
+
+/// Decompose the tiles of `map` matching `predicate` into non-overlapping rectangles.
+///
+/// This greedily grows each rectangle as wide as possible, then as tall as possible, scanning the
+/// map from the top-left. The result isn't guaranteed to be minimal, but is usually far smaller
+/// than one rectangle per tile, which makes it useful for building colliders for physics engines.
+/// The same map always produces the same rectangles, in the same order.
+/// # Example
+/// ```
+/// use tile_maps::{rect, row::StaticMap, prelude::*};
+///
+/// let map = StaticMap::from([
+///     [1, 1, 1, 0],
+///     [1, 1, 1, 0],
+///     [1, 0, 1, 1],
+/// ]);
+/// let rects = rect::decompose(&map, |&tile| tile == 1);
+/// assert_eq!(
+///     rects,
+///     [
+///         rect::Rect::new(0, 0, 3, 2),
+///         rect::Rect::new(0, 2, 1, 1),
+///         rect::Rect::new(2, 2, 2, 1),
+///     ]
+/// );
+///
+/// // Every matching tile is covered by exactly one rectangle
+/// for y in 0..map.height() {
+///     for x in 0..map.width() {
+///         let covering = rects.iter().filter(|r| r.contains(x, y)).count();
+///         assert_eq!(covering, (map.get(x, y) == Some(1)) as usize);
+///     }
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decompose<M, F>(map: &M, mut predicate: F) -> Vec<Rect>
+where
+    M: Map,
+    F: FnMut(&M::Tile) -> bool,
+{
+    let (width, height) = map.size();
+    // Tiles that match, and aren't yet covered by a rectangle
+    let mut open = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            open.push(predicate(
+                map.get_ref(x, y).expect("Coordinates are in bounds"),
+            ));
+        }
+    }
+
+    let mut rects = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            if !open[x + y * width] {
+                continue;
+            }
+            let row = y * width;
+            let rect_width = open[row + x..row + width]
+                .iter()
+                .take_while(|&&tile| tile)
+                .count();
+            let span = |y: usize| y * width + x..y * width + x + rect_width;
+            let mut rect_height = 1;
+            while y + rect_height < height && open[span(y + rect_height)].iter().all(|&t| t) {
+                rect_height += 1;
+            }
+            for y in y..y + rect_height {
+                open[span(y)].fill(false);
+            }
+            rects.push(Rect::new(x, y, rect_width, rect_height));
+        }
+    }
+    rects
+}
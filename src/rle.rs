@@ -0,0 +1,247 @@
+//! Maps that store each row as runs of identical tiles.
+
+use alloc::{vec, vec::Vec};
+
+use crate::{row::DynamicMap, Map};
+
+/// A horizontal run of identical tiles in a row of an [`RleMap`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Run<T> {
+    /// The x coordinate of the first tile in the run.
+    pub start: usize,
+    /// The number of tiles in the run.
+    pub length: usize,
+    /// The tile repeated throughout the run.
+    pub tile: T,
+}
+
+/// A [`Map`] that stores each row as a list of [`Run`]s of identical tiles, known as run-length
+/// encoding.
+///
+/// For maps with long horizontal runs of the same tile, like backgrounds, this uses far less
+/// memory than a [`DynamicMap`]. Getting a tile takes logarithmic time in the number of runs in
+/// its row.
+///
+/// `RleMap` doesn't implement [`MapMut`][crate::MapMut], as changing a tile may split a run.
+/// Instead, use [`RleMap::set()`], which keeps runs as long as possible.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RleMap<T> {
+    rows: Vec<Vec<Run<T>>>,
+    width: usize,
+}
+
+impl<T> RleMap<T> {
+    /// Create a new `RleMap`. Each tile will be initialised to the default tile.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{rle::RleMap, prelude::*};
+    /// let map = RleMap::<u8>::new(1000, 10);
+    /// assert_eq!(map.size(), (1000, 10));
+    /// assert_eq!(map.run_count(), 10);
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self
+    where
+        T: Default + Clone,
+    {
+        let row = if width == 0 {
+            Vec::new()
+        } else {
+            vec![Run {
+                start: 0,
+                length: width,
+                tile: T::default(),
+            }]
+        };
+        Self {
+            rows: vec![row; height],
+            width,
+        }
+    }
+
+    /// Create a new `RleMap` with the same tiles as `map`.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{rle::{RleMap, Run}, row::StaticMap, prelude::*};
+    /// let map = StaticMap::from([['~', '~', '~', '#', '#'], ['~', '~', '~', '~', '~']]);
+    /// let rle = RleMap::from_map(&map);
+    ///
+    /// assert_eq!(rle.run_count(), 3);
+    /// assert_eq!(rle.runs(0).unwrap()[1], Run { start: 3, length: 2, tile: '#' });
+    /// assert_eq!(rle.get(4, 1), Some('~'));
+    /// ```
+    pub fn from_map<M>(map: &M) -> Self
+    where
+        M: Map<Tile = T>,
+        T: Clone + PartialEq,
+    {
+        let rows = (0..map.height())
+            .map(|y| {
+                let mut runs: Vec<Run<T>> = Vec::new();
+                for x in 0..map.width() {
+                    let tile = map.get_ref(x, y).expect("Coordinates are in bounds");
+                    match runs.last_mut() {
+                        Some(run) if run.tile == *tile => run.length += 1,
+                        _ => runs.push(Run {
+                            start: x,
+                            length: 1,
+                            tile: tile.clone(),
+                        }),
+                    }
+                }
+                runs
+            })
+            .collect();
+        Self {
+            rows,
+            width: map.width(),
+        }
+    }
+
+    /// Get the runs making up a row, from left to right, or [`None`] if the row is out of bounds.
+    pub fn runs(&self, row: usize) -> Option<&[Run<T>]> {
+        self.rows.get(row).map(Vec::as_slice)
+    }
+
+    /// Iterate over the runs making up each row, from top to bottom.
+    pub fn run_rows(&self) -> impl DoubleEndedIterator<Item = &[Run<T>]> + '_ {
+        self.rows.iter().map(Vec::as_slice)
+    }
+
+    /// Get the total number of runs in the map.
+    pub fn run_count(&self) -> usize {
+        self.rows.iter().map(Vec::len).sum()
+    }
+
+    /// If the coordinates are in bounds of the map, sets that tile to `new` and returns [`true`].
+    /// Returns [`false`] if the coordinates were out of bounds.
+    ///
+    /// The run containing the tile is split as needed, and joined with its neighbours if they
+    /// hold the same tile.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{rle::RleMap, prelude::*};
+    /// let mut map = RleMap::<u8>::new(10, 1);
+    /// map.set(4, 0, 1);
+    /// assert_eq!(map.run_count(), 3);
+    /// map.set(5, 0, 1);
+    /// assert_eq!(map.run_count(), 3);
+    /// map.set(4, 0, 0);
+    /// map.set(5, 0, 0);
+    /// assert_eq!(map.run_count(), 1);
+    /// ```
+    pub fn set(&mut self, x: usize, y: usize, new: T) -> bool
+    where
+        T: Clone + PartialEq,
+    {
+        if !self.in_bounds(x, y) {
+            return false;
+        }
+        let runs = &mut self.rows[y];
+        let k = runs.partition_point(|run| run.start <= x) - 1;
+        let run = &runs[k];
+        if run.tile == new {
+            return true;
+        }
+
+        let (start, end) = (run.start, run.start + run.length);
+        let mut pieces = Vec::with_capacity(3);
+        if x > start {
+            pieces.push(Run {
+                start,
+                length: x - start,
+                tile: run.tile.clone(),
+            });
+        }
+        pieces.push(Run {
+            start: x,
+            length: 1,
+            tile: new,
+        });
+        if x + 1 < end {
+            pieces.push(Run {
+                start: x + 1,
+                length: end - x - 1,
+                tile: run.tile.clone(),
+            });
+        }
+        runs.splice(k..k + 1, pieces);
+
+        // Join the new run with its neighbours
+        let i = if x > start { k + 1 } else { k };
+        if runs
+            .get(i + 1)
+            .is_some_and(|next| next.tile == runs[i].tile)
+        {
+            runs[i].length += runs.remove(i + 1).length;
+        }
+        if i > 0 && runs[i - 1].tile == runs[i].tile {
+            runs[i - 1].length += runs.remove(i).length;
+        }
+        true
+    }
+}
+
+impl<T> Map for RleMap<T> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        if self.in_bounds(x, y) {
+            let runs = &self.rows[y];
+            Some(&runs[runs.partition_point(|run| run.start <= x) - 1].tile)
+        } else {
+            None
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+impl<T: Clone + PartialEq> From<DynamicMap<T>> for RleMap<T> {
+    fn from(map: DynamicMap<T>) -> Self {
+        Self::from_map(&map)
+    }
+}
+
+impl<T: Clone> From<RleMap<T>> for DynamicMap<T> {
+    /// Expand the runs of an [`RleMap`] into a [`DynamicMap`].
+    /// # Example
+    /// ```
+    /// # use tile_maps::{rle::RleMap, row::DynamicMap, prelude::*};
+    /// let map = DynamicMap::from_vec(vec![1, 1, 2, 2, 2, 2], 3).unwrap();
+    /// let rle = RleMap::from(map.clone());
+    /// assert_eq!(rle.run_count(), 3);
+    ///
+    /// let expanded = DynamicMap::from(rle);
+    /// assert_eq!(expanded.size(), (3, 2));
+    /// assert_eq!(expanded.row(0), map.row(0));
+    /// assert_eq!(expanded.row(1), map.row(1));
+    ///
+    /// // Maps without tiles keep their size
+    /// let empty = DynamicMap::<u8>::from(RleMap::from(DynamicMap::new(0, 4)));
+    /// assert_eq!(empty.size(), (0, 4));
+    /// assert_eq!(empty.rows().count(), 4);
+    /// let empty = DynamicMap::<u8>::from(RleMap::new(4, 0));
+    /// assert_eq!(empty.size(), (4, 0));
+    /// ```
+    fn from(map: RleMap<T>) -> Self {
+        let (width, height) = map.size();
+        let mut tiles = Vec::with_capacity(width * height);
+        for run in map.rows.into_iter().flatten() {
+            tiles.extend(core::iter::repeat_n(run.tile, run.length));
+        }
+        DynamicMap::from_rows(tiles, width, height)
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use super::{MapRows, MapRowsMut};
+use crate::{Map, MapMut};
+
+/// A [`Map`] that stores up to `CAPACITY` tiles inline, in a fixed-size array, and can be
+/// resized while it fits.
+///
+/// This gives maps whose size is only known at runtime without needing an allocator. If the size
+/// is known at compile time, use a [`StaticMap`][super::StaticMap], or if an allocator is
+/// available, a [`DynamicMap`][super::DynamicMap].
+#[derive(Clone)]
+pub struct ArrayMap<T, const CAPACITY: usize> {
+    tiles: [T; CAPACITY],
+    width: usize,
+    height: usize,
+}
+
+impl<T: Default, const CAPACITY: usize> ArrayMap<T, CAPACITY> {
+    /// Create a new `ArrayMap`. Each tile will be initialised to the default tile.
+    ///
+    /// Returns [`None`] if the map has more than `CAPACITY` tiles.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{row::ArrayMap, prelude::*};
+    /// let map = ArrayMap::<i32, 64>::new(5, 4).unwrap();
+    /// assert_eq!(map.size(), (5, 4));
+    ///
+    /// assert!(ArrayMap::<i32, 64>::new(10, 10).is_none());
+    /// ```
+    pub fn new(width: usize, height: usize) -> Option<Self> {
+        if fits(width, height, CAPACITY) {
+            Some(Self {
+                tiles: core::array::from_fn(|_| T::default()),
+                width,
+                height,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Resize the map. Tiles that are still in bounds keep their coordinates, and new tiles will
+    /// be initialised to the default tile.
+    ///
+    /// Returns [`false`], without changing anything, if the new size has more than `CAPACITY`
+    /// tiles.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{row::ArrayMap, prelude::*};
+    /// let mut map = ArrayMap::<i32, 16>::new(3, 2).unwrap();
+    /// map.row_mut(0).unwrap().copy_from_slice(&[1, 2, 3]);
+    /// map.row_mut(1).unwrap().copy_from_slice(&[4, 5, 6]);
+    ///
+    /// assert!(map.resize(4, 3));
+    /// assert_eq!(map.row(0), Some([1, 2, 3, 0].as_slice()));
+    /// assert_eq!(map.row(1), Some([4, 5, 6, 0].as_slice()));
+    /// assert_eq!(map.row(2), Some([0, 0, 0, 0].as_slice()));
+    ///
+    /// assert!(map.resize(2, 2));
+    /// assert_eq!(map.row(1), Some([4, 5].as_slice()));
+    ///
+    /// assert!(!map.resize(5, 5));
+    /// assert_eq!(map.size(), (2, 2));
+    /// ```
+    pub fn resize(&mut self, width: usize, height: usize) -> bool {
+        if !fits(width, height, CAPACITY) {
+            return false;
+        }
+        let (old_width, old_height) = (self.width, self.height);
+        let (kept_width, kept_height) = (old_width.min(width), old_height.min(height));
+        let mut move_tile = |x: usize, y: usize| {
+            let tile = core::mem::take(&mut self.tiles[x + y * old_width]);
+            self.tiles[x + y * width] = tile;
+        };
+        // Move tiles in an order that never overwrites a tile before it's moved
+        if width <= old_width {
+            for y in 0..kept_height {
+                for x in 0..kept_width {
+                    move_tile(x, y);
+                }
+            }
+        } else {
+            for y in (0..kept_height).rev() {
+                for x in (0..kept_width).rev() {
+                    move_tile(x, y);
+                }
+            }
+        }
+        for (i, tile) in self.tiles.iter_mut().enumerate() {
+            let kept = width != 0 && i % width < kept_width && i / width < kept_height;
+            if !kept {
+                *tile = T::default();
+            }
+        }
+        self.width = width;
+        self.height = height;
+        true
+    }
+}
+
+impl<T, const CAPACITY: usize> ArrayMap<T, CAPACITY> {
+    /// Get the maximum number of tiles the map can hold.
+    pub fn capacity(&self) -> usize {
+        CAPACITY
+    }
+}
+
+impl<T, const CAPACITY: usize> Map for ArrayMap<T, CAPACITY> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        if self.in_bounds(x, y) {
+            self.tiles.get(x + y * self.width)
+        } else {
+            None
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<T, const CAPACITY: usize> MapMut for ArrayMap<T, CAPACITY> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Tile> {
+        if self.in_bounds(x, y) {
+            self.tiles.get_mut(x + y * self.width)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const CAPACITY: usize> MapRows for ArrayMap<T, CAPACITY> {
+    fn row(&self, row: usize) -> Option<&[Self::Tile]> {
+        if row < self.height {
+            self.tiles.get(row * self.width..(row + 1) * self.width)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn rows(&self) -> Box<dyn DoubleEndedIterator<Item = &[Self::Tile]> + '_> {
+        let width = self.width;
+        Box::new((0..self.height).map(move |y| &self.tiles[y * width..(y + 1) * width]))
+    }
+}
+
+impl<T, const CAPACITY: usize> MapRowsMut for ArrayMap<T, CAPACITY> {
+    fn row_mut(&mut self, row: usize) -> Option<&mut [Self::Tile]> {
+        if row < self.height {
+            self.tiles.get_mut(row * self.width..(row + 1) * self.width)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn rows_mut(&mut self) -> Box<dyn DoubleEndedIterator<Item = &mut [Self::Tile]> + '_> {
+        let (width, height) = (self.width, self.height);
+        let tiles = &mut self.tiles[..width * height];
+        if width == 0 {
+            return Box::new((0..height).map(|_| <&mut [T]>::default()));
+        }
+        Box::new(tiles.chunks_mut(width))
+    }
+}
+
+/// Test if a map of the given size fits in `capacity` tiles.
+fn fits(width: usize, height: usize, capacity: usize) -> bool {
+    width
+        .checked_mul(height)
+        .is_some_and(|size| size <= capacity)
+}
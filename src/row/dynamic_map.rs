@@ -2,7 +2,10 @@
 use alloc::{boxed::Box, vec::Vec};
 
 use super::{MapRows, MapRowsMut};
-use crate::{Map, MapMut};
+use crate::{
+    diff::{self, Patch},
+    Map, MapMut,
+};
 
 /// A [`Map`] that heap allocates its tiles.
 ///
@@ -11,6 +14,7 @@ use crate::{Map, MapMut};
 pub struct DynamicMap<T> {
     tiles: Vec<T>,
     width: usize,
+    height: usize,
 }
 
 impl<T> DynamicMap<T> {
@@ -31,8 +35,105 @@ impl<T> DynamicMap<T> {
                 .take(width * height)
                 .collect(),
             width,
+            height,
         }
     }
+
+    /// Create a new `DynamicMap` from a [`Vec`] of tiles, stored in row-major order.
+    ///
+    /// Returns [`None`] if `width` is zero, or the number of tiles is not a multiple of `width`.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{row::DynamicMap, prelude::*};
+    /// let map = DynamicMap::from_vec(vec![1, 2, 3, 4, 5, 6], 3).unwrap();
+    /// assert_eq!(map.size(), (3, 2));
+    /// assert_eq!(map.get(0, 1), Some(4));
+    ///
+    /// assert!(DynamicMap::from_vec(vec![1, 2, 3, 4], 3).is_none());
+    /// ```
+    pub fn from_vec(tiles: Vec<T>, width: usize) -> Option<Self> {
+        if width != 0 && tiles.len().is_multiple_of(width) {
+            let height = tiles.len() / width;
+            Some(Self {
+                tiles,
+                width,
+                height,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Create a `DynamicMap` from `width` by `height` tiles, stored in row-major order. Unlike
+    /// [`DynamicMap::from_vec()`], the map may have no columns.
+    pub(crate) fn from_rows(tiles: Vec<T>, width: usize, height: usize) -> Self {
+        debug_assert_eq!(tiles.len(), width * height);
+        Self {
+            tiles,
+            width,
+            height,
+        }
+    }
+
+    /// Resize the map. Tiles that are still in bounds keep their coordinates, and new tiles will
+    /// be initialised to the default tile.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{row::DynamicMap, prelude::*};
+    /// let mut map = DynamicMap::from_vec(vec![1, 2, 3, 4, 5, 6], 3).unwrap();
+    /// map.resize(2, 3);
+    /// assert_eq!(map.size(), (2, 3));
+    /// assert_eq!(map.row(0), Some([1, 2].as_slice()));
+    /// assert_eq!(map.row(1), Some([4, 5].as_slice()));
+    /// assert_eq!(map.row(2), Some([0, 0].as_slice()));
+    /// ```
+    pub fn resize(&mut self, width: usize, height: usize)
+    where
+        T: Default,
+    {
+        self.resize_with(width, height, T::default);
+    }
+
+    /// Resize the map. Tiles that are still in bounds keep their coordinates, and new tiles will
+    /// be initialised by calling `f`.
+    pub fn resize_with<F>(&mut self, width: usize, height: usize, mut f: F)
+    where
+        F: FnMut() -> T,
+    {
+        let (old_width, old_height) = (self.width, self.height());
+        let mut old = core::mem::take(&mut self.tiles).into_iter();
+        let mut tiles = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let mut kept = 0;
+            if y < old_height {
+                let mut row = old.by_ref().take(old_width);
+                for tile in row.by_ref().take(width) {
+                    tiles.push(tile);
+                    kept += 1;
+                }
+                row.for_each(drop);
+            }
+            tiles.extend(core::iter::repeat_with(&mut f).take(width - kept));
+        }
+        self.tiles = tiles;
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Shrink the map, keeping the tiles that are still in bounds. The new size mustn't have any
+    /// tiles outside the old map.
+    pub(crate) fn truncate(&mut self, width: usize, height: usize) {
+        debug_assert!(width * height == 0 || (width <= self.width && height <= self.height));
+        let old_width = self.width;
+        let mut i = 0;
+        self.tiles.retain(|_| {
+            let kept = i % old_width < width && i / old_width < height;
+            i += 1;
+            kept
+        });
+        self.width = width;
+        self.height = height;
+    }
 }
 
 impl<T> Map for DynamicMap<T> {
@@ -42,7 +143,7 @@ impl<T> Map for DynamicMap<T> {
     where
         Self::Tile: Copy,
     {
-        if x < self.width {
+        if self.in_bounds(x, y) {
             self.tiles.get(x + y * self.width).copied()
         } else {
             None
@@ -50,7 +151,7 @@ impl<T> Map for DynamicMap<T> {
     }
 
     fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
-        if x < self.width {
+        if self.in_bounds(x, y) {
             self.tiles.get(x + y * self.width)
         } else {
             None
@@ -62,19 +163,26 @@ impl<T> Map for DynamicMap<T> {
     }
 
     fn height(&self) -> usize {
-        self.tiles.len() / self.width
+        self.height
     }
 }
 
 impl<T> MapMut for DynamicMap<T> {
     fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Tile> {
-        if x < self.width {
+        if self.in_bounds(x, y) {
             self.tiles.get_mut(x + y * self.width)
         } else {
             None
         }
     }
 
+    fn apply_patch(&mut self, patch: &Patch<Self::Tile>) -> bool
+    where
+        Self::Tile: Clone,
+    {
+        diff::apply_resizing(self, patch)
+    }
+
     fn clear(&mut self)
     where
         Self::Tile: Default,
@@ -98,22 +206,36 @@ impl<T> MapMut for DynamicMap<T> {
 
 impl<T> MapRows for DynamicMap<T> {
     fn row(&self, row: usize) -> Option<&[Self::Tile]> {
-        self.tiles.get(row * self.width..(row + 1) * self.width)
+        if row < self.height {
+            self.tiles.get(row * self.width..(row + 1) * self.width)
+        } else {
+            None
+        }
     }
 
     #[cfg(feature = "alloc")]
     fn rows(&self) -> Box<dyn DoubleEndedIterator<Item = &[Self::Tile]> + '_> {
+        if self.width == 0 {
+            return Box::new((0..self.height).map(|_| <&[T]>::default()));
+        }
         Box::new(self.tiles.chunks(self.width))
     }
 }
 
 impl<T> MapRowsMut for DynamicMap<T> {
     fn row_mut(&mut self, row: usize) -> Option<&mut [Self::Tile]> {
-        self.tiles.get_mut(row * self.width..(row + 1) * self.width)
+        if row < self.height {
+            self.tiles.get_mut(row * self.width..(row + 1) * self.width)
+        } else {
+            None
+        }
     }
 
     #[cfg(feature = "alloc")]
     fn rows_mut(&mut self) -> Box<dyn DoubleEndedIterator<Item = &mut [Self::Tile]> + '_> {
+        if self.width == 0 {
+            return Box::new((0..self.height).map(|_| <&mut [T]>::default()));
+        }
         Box::new(self.tiles.chunks_mut(self.width))
     }
 }
@@ -3,6 +3,10 @@
 //! If your maps are a fixed size, or they are primarily resized by adding or removing rows, these
 //! [`Map`] implementations offer the best performance.
 
+mod array_map;
+pub use array_map::ArrayMap;
+mod slice_map;
+pub use slice_map::{SliceMap, SliceMapMut};
 mod static_map;
 pub use static_map::StaticMap;
 #[cfg(feature = "alloc")]
@@ -0,0 +1,302 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use super::{MapRows, MapRowsMut};
+use crate::{Map, MapMut};
+
+/// A [`Map`] that borrows its tiles from a slice, stored in row-major order.
+///
+/// This is useful when tiles are owned by something else, like a game engine or a memory-mapped
+/// file. Rows can be spaced further apart than the width of the map, by giving a stride, so maps
+/// can also view part of a larger buffer.
+///
+/// For a mutable view, see [`SliceMapMut`].
+#[derive(Clone, Copy, Debug)]
+pub struct SliceMap<'a, T> {
+    tiles: &'a [T],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl<'a, T> SliceMap<'a, T> {
+    /// Create a new `SliceMap`, with rows of `width` tiles laid out one after another.
+    ///
+    /// Returns [`None`] if `width` is zero, or the number of tiles is not a multiple of `width`.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{row::SliceMap, prelude::*};
+    /// let tiles = [1, 2, 3, 4, 5, 6];
+    /// let map = SliceMap::new(&tiles, 2).unwrap();
+    /// assert_eq!(map.size(), (2, 3));
+    /// assert_eq!(map.get(1, 2), Some(6));
+    ///
+    /// assert!(SliceMap::new(&tiles, 4).is_none());
+    /// ```
+    pub fn new(tiles: &'a [T], width: usize) -> Option<Self> {
+        if width != 0 && tiles.len().is_multiple_of(width) {
+            Some(Self {
+                tiles,
+                width,
+                height: tiles.len() / width,
+                stride: width,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Create a new `SliceMap` of `width` by `height` tiles, where each row starts `stride`
+    /// tiles after the one before.
+    ///
+    /// Returns [`None`] if `stride` is zero or less than `width`, or there aren't enough tiles.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{row::SliceMap, prelude::*};
+    /// // View the middle of a 4 by 3 buffer
+    /// let tiles = [
+    ///     0, 0, 0, 0,
+    ///     0, 1, 2, 0,
+    ///     0, 3, 4, 0,
+    /// ];
+    /// let map = SliceMap::with_stride(&tiles[5..], 2, 2, 4).unwrap();
+    /// assert_eq!(map.row(0), Some([1, 2].as_slice()));
+    /// assert_eq!(map.row(1), Some([3, 4].as_slice()));
+    ///
+    /// assert!(SliceMap::with_stride(&tiles, 2, usize::MAX, 4).is_none());
+    /// ```
+    pub fn with_stride(tiles: &'a [T], width: usize, height: usize, stride: usize) -> Option<Self> {
+        if fits(tiles.len(), width, height, stride) {
+            Some(Self {
+                tiles,
+                width,
+                height,
+                stride,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Get the number of tiles from the start of one row to the start of the next.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+}
+
+impl<T> Map for SliceMap<'_, T> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        if self.in_bounds(x, y) {
+            self.tiles.get(x + y * self.stride)
+        } else {
+            None
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<T> MapRows for SliceMap<'_, T> {
+    fn row(&self, row: usize) -> Option<&[Self::Tile]> {
+        row_range(row, self.width, self.height, self.stride).map(|range| &self.tiles[range])
+    }
+
+    #[cfg(feature = "alloc")]
+    fn rows(&self) -> Box<dyn DoubleEndedIterator<Item = &[Self::Tile]> + '_> {
+        let width = self.width;
+        Box::new(
+            self.tiles
+                .chunks(self.stride)
+                .take(self.height)
+                .map(move |row| &row[..width]),
+        )
+    }
+}
+
+/// A [`Map`] that mutably borrows its tiles from a slice, stored in row-major order.
+///
+/// This is the mutable counterpart of [`SliceMap`].
+#[derive(Debug)]
+pub struct SliceMapMut<'a, T> {
+    tiles: &'a mut [T],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl<'a, T> SliceMapMut<'a, T> {
+    /// Create a new `SliceMapMut`, with rows of `width` tiles laid out one after another.
+    ///
+    /// Returns [`None`] if `width` is zero, or the number of tiles is not a multiple of `width`.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{row::SliceMapMut, prelude::*};
+    /// let mut tiles = [0; 6];
+    /// let mut map = SliceMapMut::new(&mut tiles, 3).unwrap();
+    /// map.set(1, 1, 7);
+    /// assert_eq!(tiles, [0, 0, 0, 0, 7, 0]);
+    /// ```
+    pub fn new(tiles: &'a mut [T], width: usize) -> Option<Self> {
+        if width != 0 && tiles.len().is_multiple_of(width) {
+            let height = tiles.len() / width;
+            Some(Self {
+                tiles,
+                width,
+                height,
+                stride: width,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Create a new `SliceMapMut` of `width` by `height` tiles, where each row starts `stride`
+    /// tiles after the one before.
+    ///
+    /// Returns [`None`] if `stride` is zero or less than `width`, or there aren't enough tiles.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{row::SliceMapMut, prelude::*};
+    /// let mut tiles = [0; 12];
+    /// let mut map = SliceMapMut::with_stride(&mut tiles[5..], 2, 2, 4).unwrap();
+    /// map.clear_to(1);
+    /// assert_eq!(tiles, [
+    ///     0, 0, 0, 0,
+    ///     0, 1, 1, 0,
+    ///     0, 1, 1, 0,
+    /// ]);
+    /// ```
+    pub fn with_stride(
+        tiles: &'a mut [T],
+        width: usize,
+        height: usize,
+        stride: usize,
+    ) -> Option<Self> {
+        if fits(tiles.len(), width, height, stride) {
+            Some(Self {
+                tiles,
+                width,
+                height,
+                stride,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Get the number of tiles from the start of one row to the start of the next.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+}
+
+impl<T> Map for SliceMapMut<'_, T> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        if self.in_bounds(x, y) {
+            self.tiles.get(x + y * self.stride)
+        } else {
+            None
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<T> MapMut for SliceMapMut<'_, T> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Tile> {
+        if self.in_bounds(x, y) {
+            self.tiles.get_mut(x + y * self.stride)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> MapRows for SliceMapMut<'_, T> {
+    fn row(&self, row: usize) -> Option<&[Self::Tile]> {
+        row_range(row, self.width, self.height, self.stride).map(|range| &self.tiles[range])
+    }
+
+    #[cfg(feature = "alloc")]
+    fn rows(&self) -> Box<dyn DoubleEndedIterator<Item = &[Self::Tile]> + '_> {
+        let width = self.width;
+        Box::new(
+            self.tiles
+                .chunks(self.stride)
+                .take(self.height)
+                .map(move |row| &row[..width]),
+        )
+    }
+}
+
+impl<T> MapRowsMut for SliceMapMut<'_, T> {
+    fn row_mut(&mut self, row: usize) -> Option<&mut [Self::Tile]> {
+        row_range(row, self.width, self.height, self.stride).map(|range| &mut self.tiles[range])
+    }
+
+    #[cfg(feature = "alloc")]
+    fn rows_mut(&mut self) -> Box<dyn DoubleEndedIterator<Item = &mut [Self::Tile]> + '_> {
+        let width = self.width;
+        Box::new(
+            self.tiles
+                .chunks_mut(self.stride)
+                .take(self.height)
+                .map(move |row| &mut row[..width]),
+        )
+    }
+}
+
+/// Test if `len` tiles are enough for a map with the given dimensions and stride.
+fn fits(len: usize, width: usize, height: usize, stride: usize) -> bool {
+    let needed = match height.checked_sub(1) {
+        Some(last) => last
+            .checked_mul(stride)
+            .and_then(|start| start.checked_add(width)),
+        None => Some(0),
+    };
+    stride != 0 && stride >= width && needed.is_some_and(|needed| needed <= len)
+}
+
+/// Get the range of tiles making up a row, or [`None`] if it's out of bounds.
+fn row_range(
+    row: usize,
+    width: usize,
+    height: usize,
+    stride: usize,
+) -> Option<core::ops::Range<usize>> {
+    if row < height {
+        Some(row * stride..row * stride + width)
+    } else {
+        None
+    }
+}
@@ -43,6 +43,22 @@ where
     }
 }
 
+impl<T, const WIDTH: usize, const HEIGHT: usize> From<[[T; WIDTH]; HEIGHT]>
+    for StaticMap<T, WIDTH, HEIGHT>
+{
+    /// Create a `StaticMap` from an array of rows.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{row::StaticMap, prelude::*};
+    /// let map = StaticMap::from([[1, 2, 3], [4, 5, 6]]);
+    /// assert_eq!(map.size(), (3, 2));
+    /// assert_eq!(map.get(2, 1), Some(6));
+    /// ```
+    fn from(tiles: [[T; WIDTH]; HEIGHT]) -> Self {
+        Self { tiles }
+    }
+}
+
 impl<T, const WIDTH: usize, const HEIGHT: usize> Map for StaticMap<T, WIDTH, HEIGHT> {
     type Tile = T;
 
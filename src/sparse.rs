@@ -0,0 +1,227 @@
+//! Maps that only store the tiles that aren't the default tile.
+
+use hashbrown::HashMap;
+
+use crate::{Map, MapMut};
+
+/// A [`Map`] that stores only its non-default tiles, in a hash map.
+///
+/// This uses far less memory than a [`DynamicMap`][crate::row::DynamicMap] for maps that are
+/// mostly empty, like layers of objects, at the cost of slower access to each tile. Tiles that
+/// aren't stored are the default tile.
+///
+/// Setting a tile to the default tile removes it, and clearing the map to the default tile
+/// removes every tile. Getting a mutable reference to a tile with [`MapMut::get_mut()`] stores
+/// it, even if it's left as the default tile, so [`prune()`][SparseMap::prune()] removes any
+/// default tiles left behind.
+/// # Example
+/// ```
+/// # use tile_maps::{sparse::SparseMap, prelude::*};
+/// let mut map = SparseMap::<u8>::new(1000, 1000);
+/// map.set(10, 20, 3);
+/// map.set(30, 40, 5);
+/// assert_eq!(map.len(), 2);
+///
+/// map.set(10, 20, 0);
+/// assert_eq!(map.replace(30, 40, 0), Ok(5));
+/// assert!(map.is_empty());
+///
+/// map.set(10, 20, 3);
+/// map.clear_to(0);
+/// assert!(map.is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SparseMap<T> {
+    tiles: HashMap<(usize, usize), T>,
+    default: T,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Default> SparseMap<T> {
+    /// Create a new, empty `SparseMap`, where every tile is the default tile.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{sparse::SparseMap, prelude::*};
+    /// let mut map = SparseMap::<Option<char>>::new(1000, 1000);
+    /// assert_eq!(map.size(), (1000, 1000));
+    /// assert_eq!(map.get(500, 500), Some(None));
+    ///
+    /// map.set(500, 500, Some('@'));
+    /// assert_eq!(map.get(500, 500), Some(Some('@')));
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            tiles: HashMap::new(),
+            default: T::default(),
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> SparseMap<T> {
+    /// Get the number of stored tiles.
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Test if no tiles are stored, so every tile is the default tile.
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Iterate over the stored tiles, and their coordinates (x, y), in no particular order.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{sparse::SparseMap, prelude::*};
+    /// let mut map = SparseMap::<u8>::new(100, 100);
+    /// map.set(10, 20, 3);
+    /// map.set(30, 40, 5);
+    ///
+    /// let mut tiles = map.iter().collect::<Vec<_>>();
+    /// tiles.sort();
+    /// assert_eq!(tiles, [((10, 20), &3), ((30, 40), &5)]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.tiles.iter().map(|(&coords, tile)| (coords, tile))
+    }
+
+    /// Iterate mutably over the stored tiles, and their coordinates (x, y), in no particular
+    /// order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> + '_ {
+        self.tiles.iter_mut().map(|(&coords, tile)| (coords, tile))
+    }
+
+    /// Remove a tile, leaving the default tile in its place.
+    ///
+    /// Returns the removed tile, or [`None`] if it wasn't stored.
+    pub fn remove(&mut self, x: usize, y: usize) -> Option<T> {
+        self.tiles.remove(&(x, y))
+    }
+
+    /// Remove every stored tile that's equal to the default tile.
+    /// # Example
+    /// ```
+    /// # use tile_maps::{sparse::SparseMap, prelude::*};
+    /// let mut map = SparseMap::<u8>::new(100, 100);
+    /// *map.get_mut(10, 20).unwrap() += 1;
+    /// *map.get_mut(30, 40).unwrap() += 0;
+    /// assert_eq!(map.len(), 2);
+    ///
+    /// map.prune();
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn prune(&mut self)
+    where
+        T: PartialEq,
+    {
+        let default = &self.default;
+        self.tiles.retain(|_, tile| tile != default);
+    }
+
+    /// Shrink the memory used to store tiles as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.tiles.shrink_to_fit();
+    }
+}
+
+impl<T> Map for SparseMap<T> {
+    type Tile = T;
+
+    fn get(&self, x: usize, y: usize) -> Option<Self::Tile>
+    where
+        Self::Tile: Copy,
+    {
+        self.get_ref(x, y).copied()
+    }
+
+    fn get_ref(&self, x: usize, y: usize) -> Option<&Self::Tile> {
+        if self.in_bounds(x, y) {
+            Some(self.tiles.get(&(x, y)).unwrap_or(&self.default))
+        } else {
+            None
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl<T: Default + PartialEq> MapMut for SparseMap<T> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Self::Tile> {
+        if self.in_bounds(x, y) {
+            Some(self.tiles.entry((x, y)).or_default())
+        } else {
+            None
+        }
+    }
+
+    fn replace(&mut self, x: usize, y: usize, new: Self::Tile) -> Result<Self::Tile, Self::Tile> {
+        if !self.in_bounds(x, y) {
+            Err(new)
+        } else if new == self.default {
+            Ok(self.tiles.remove(&(x, y)).unwrap_or_default())
+        } else {
+            Ok(self.tiles.insert((x, y), new).unwrap_or_default())
+        }
+    }
+
+    fn replace_default(&mut self, x: usize, y: usize) -> Result<Self::Tile, Self::Tile> {
+        if self.in_bounds(x, y) {
+            Ok(self.tiles.remove(&(x, y)).unwrap_or_default())
+        } else {
+            Err(T::default())
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, new: Self::Tile) -> bool {
+        if !self.in_bounds(x, y) {
+            return false;
+        }
+        if new == self.default {
+            self.tiles.remove(&(x, y));
+        } else {
+            self.tiles.insert((x, y), new);
+        }
+        true
+    }
+
+    fn set_default(&mut self, x: usize, y: usize) -> bool {
+        if self.in_bounds(x, y) {
+            self.tiles.remove(&(x, y));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn clear(&mut self) {
+        self.tiles.clear();
+    }
+
+    /// Clears the entire map to `new`.
+    ///
+    /// Clearing to the default tile removes every tile. Any other tile is stored for every
+    /// coordinate in the map, so a [`DynamicMap`][crate::row::DynamicMap] suits maps full of
+    /// non-default tiles far better.
+    fn clear_to(&mut self, new: Self::Tile)
+    where
+        Self::Tile: Clone,
+    {
+        self.tiles.clear();
+        if new != self.default {
+            for y in 0..self.height {
+                for x in 0..self.width {
+                    self.tiles.insert((x, y), new.clone());
+                }
+            }
+        }
+    }
+}
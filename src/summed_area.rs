@@ -0,0 +1,157 @@
+//! Summed-area tables, for constant-time sums over rectangular areas of a [`Map`].
+
+use alloc::vec::Vec;
+use core::ops::{Add, Sub};
+
+use crate::Map;
+
+/// A table of prefix sums over a [`Map`] of numeric tiles.
+///
+/// Once built, the sum of the tiles in any rectangle of the map can be found in constant time,
+/// no matter how large the rectangle is. To count the tiles matching a predicate instead, see
+/// [`SummedAreaTable::count()`].
+///
+/// The table doesn't borrow the map it was built from, so it won't notice when the map changes.
+/// After changing a tile, call [`update()`][SummedAreaTable::update()] with its new value.
+#[derive(Clone)]
+pub struct SummedAreaTable<T> {
+    /// Sums of all the tiles above and to the left of each position, with an extra row and
+    /// column of zeros at the top and left.
+    sums: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> SummedAreaTable<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// Build a `SummedAreaTable` over the tiles of `map`.
+    /// # Example
+    /// ```
+    /// use tile_maps::{row::StaticMap, summed_area::SummedAreaTable};
+    ///
+    /// let map = StaticMap::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+    /// let table = SummedAreaTable::new(&map);
+    /// assert_eq!(table.sum(0, 0, 3, 3), Some(45));
+    /// assert_eq!(table.sum(1, 1, 2, 2), Some(5 + 6 + 8 + 9));
+    /// ```
+    pub fn new<M: Map<Tile = T>>(map: &M) -> Self {
+        Self::new_with(map, |&tile| tile)
+    }
+
+    /// Build a `SummedAreaTable` over the values returned by `value` for each tile of `map`.
+    /// # Example
+    /// ```
+    /// use tile_maps::{row::StaticMap, summed_area::SummedAreaTable};
+    ///
+    /// let map = StaticMap::from([['#', '.'], ['.', '#']]);
+    /// let table = SummedAreaTable::new_with(&map, |&tile| if tile == '#' { 2.5 } else { 0.5 });
+    /// assert_eq!(table.sum(0, 0, 2, 2), Some(6.0));
+    /// ```
+    pub fn new_with<M, F>(map: &M, mut value: F) -> Self
+    where
+        M: Map,
+        F: FnMut(&M::Tile) -> T,
+    {
+        let (width, height) = map.size();
+        let stride = width + 1;
+        let mut sums = Vec::with_capacity(stride * (height + 1));
+        sums.resize(stride, T::default());
+        for y in 0..height {
+            let mut row_sum = T::default();
+            sums.push(T::default());
+            for x in 0..width {
+                row_sum = row_sum + value(map.get_ref(x, y).expect("Coordinates are in bounds"));
+                let above = sums[x + 1 + y * stride];
+                sums.push(above + row_sum);
+            }
+        }
+        Self {
+            sums,
+            width,
+            height,
+        }
+    }
+
+    /// Get the sum of the tiles in the rectangle with its top-left corner at `(x, y)`. Returns
+    /// [`None`] if any part of the rectangle is out of bounds.
+    pub fn sum(&self, x: usize, y: usize, width: usize, height: usize) -> Option<T> {
+        let right = x.checked_add(width)?;
+        let bottom = y.checked_add(height)?;
+        if right > self.width || bottom > self.height {
+            return None;
+        }
+        let stride = self.width + 1;
+        Some(
+            self.sums[right + bottom * stride] + self.sums[x + y * stride]
+                - self.sums[x + bottom * stride]
+                - self.sums[right + y * stride],
+        )
+    }
+
+    /// Change the value of the tile at `(x, y)` to `value`, updating the sums that include it.
+    ///
+    /// This takes time proportional to the number of tiles below and to the right of `(x, y)`, so
+    /// after changing large parts of the map, it may be faster to build a new table.
+    ///
+    /// Returns [`false`] if the coordinates are out of bounds.
+    /// # Example
+    /// ```
+    /// use tile_maps::{row::StaticMap, summed_area::SummedAreaTable, prelude::*};
+    ///
+    /// let mut map = StaticMap::<bool, 4, 4>::new();
+    /// let mut walls = SummedAreaTable::count(&map, |&wall| wall);
+    ///
+    /// map.set(2, 1, true);
+    /// walls.update(2, 1, 1);
+    /// assert_eq!(walls.sum(0, 0, 4, 4), Some(1));
+    /// assert_eq!(walls.sum(0, 0, 2, 4), Some(0));
+    /// ```
+    pub fn update(&mut self, x: usize, y: usize, value: T) -> bool {
+        let Some(old) = self.sum(x, y, 1, 1) else {
+            return false;
+        };
+        let stride = self.width + 1;
+        for row in self.sums[(y + 1) * stride..].chunks_mut(stride) {
+            for sum in &mut row[x + 1..] {
+                // Subtract first, so unsigned sums can't underflow
+                *sum = *sum - old + value;
+            }
+        }
+        true
+    }
+
+    /// Get the width of the table, in tiles.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the table, in tiles.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+}
+
+impl SummedAreaTable<usize> {
+    /// Build a `SummedAreaTable` counting the tiles of `map` that match `predicate`.
+    /// # Example
+    /// ```
+    /// use tile_maps::{row::DynamicMap, summed_area::SummedAreaTable, prelude::*};
+    ///
+    /// let mut map = DynamicMap::<bool>::new(10, 10);
+    /// map.region_mut(2, 2, 3, 3).unwrap().clear_to(true);
+    ///
+    /// let walls = SummedAreaTable::count(&map, |&wall| wall);
+    /// assert_eq!(walls.sum(0, 0, 7, 7), Some(9));
+    /// assert_eq!(walls.sum(3, 3, 7, 7), Some(4));
+    /// assert_eq!(walls.sum(4, 4, 7, 7), None);
+    /// ```
+    pub fn count<M, F>(map: &M, mut predicate: F) -> Self
+    where
+        M: Map,
+        F: FnMut(&M::Tile) -> bool,
+    {
+        Self::new_with(map, |tile| predicate(tile) as usize)
+    }
+}
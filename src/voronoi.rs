@@ -0,0 +1,126 @@
+//! Partitioning [`Map`]s into regions around seed points.
+
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use crate::{row::DynamicMap, Map, MapMut};
+
+/// How to measure the distance from a tile to a seed. See [`partition()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Distance {
+    /// Straight-line (Euclidean) distance, ignoring any obstacles in the way.
+    #[default]
+    Straight,
+    /// The length of the shortest path through passable tiles, moving horizontally and
+    /// vertically. Tiles that can't reach any seed are left unassigned.
+    Walkable,
+}
+
+/// Assign each passable tile of `map` to its nearest seed.
+///
+/// Returns a map of labels, where each tile holds the index into `seeds` of its nearest seed, or
+/// [`None`] for impassable and unassigned tiles. Where several seeds are equally near, the one
+/// with the lowest index wins. Seeds that are out of bounds, or on impassable tiles, are ignored.
+///
+/// To partition every tile, use a predicate like `|_| true`.
+/// # Example
+/// ```
+/// use tile_maps::{row::StaticMap, voronoi::{self, Distance}, prelude::*};
+///
+/// let map = StaticMap::from([
+///     ['.', '.', '#', '.'],
+///     ['.', '.', '#', '.'],
+///     ['.', '.', '#', '.'],
+///     ['.', '.', '.', '.'],
+/// ]);
+/// let seeds = [(1, 0), (3, 3)];
+///
+/// let straight = voronoi::partition(&map, &seeds, Distance::Straight, |&t| t != '#');
+/// assert_eq!(straight.get(0, 0), Some(Some(0)));
+/// assert_eq!(straight.get(3, 0), Some(Some(0)));
+/// assert_eq!(straight.get(2, 0), Some(None));
+///
+/// // Walking around the wall, the first seed is much further away
+/// let walked = voronoi::partition(&map, &seeds, Distance::Walkable, |&t| t != '#');
+/// assert_eq!(walked.get(0, 0), Some(Some(0)));
+/// assert_eq!(walked.get(3, 0), Some(Some(1)));
+/// ```
+pub fn partition<M, F>(
+    map: &M,
+    seeds: &[(usize, usize)],
+    distance: Distance,
+    mut passable: F,
+) -> DynamicMap<Option<usize>>
+where
+    M: Map,
+    F: FnMut(&M::Tile) -> bool,
+{
+    let (width, height) = map.size();
+    let mut open = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            open.push(passable(
+                map.get_ref(x, y).expect("Coordinates are in bounds"),
+            ));
+        }
+    }
+    let seeds = seeds
+        .iter()
+        .enumerate()
+        .filter(|&(_, &(x, y))| map.in_bounds(x, y) && open[x + y * width])
+        .collect::<Vec<_>>();
+
+    let mut labels = DynamicMap::new(width, height);
+    match distance {
+        Distance::Straight => {
+            for y in 0..height {
+                for x in 0..width {
+                    if !open[x + y * width] {
+                        continue;
+                    }
+                    let nearest = seeds.iter().min_by_key(|&&(_, &(sx, sy))| {
+                        let (dx, dy) = (sx.abs_diff(x), sy.abs_diff(y));
+                        dx * dx + dy * dy
+                    });
+                    labels.set(x, y, nearest.map(|&(i, _)| i));
+                }
+            }
+        }
+        Distance::Walkable => {
+            // Breadth-first search from every seed at once, a layer of tiles at a time
+            let mut steps = vec![usize::MAX; width * height];
+            let mut queue = VecDeque::new();
+            for &(i, &(x, y)) in &seeds {
+                if steps[x + y * width] == usize::MAX {
+                    steps[x + y * width] = 0;
+                    labels.set(x, y, Some(i));
+                    queue.push_back((x, y));
+                }
+            }
+            while let Some((x, y)) = queue.pop_front() {
+                let label = labels.get(x, y).flatten();
+                let next = steps[x + y * width] + 1;
+                let neighbours = [
+                    (x.wrapping_sub(1), y),
+                    (x + 1, y),
+                    (x, y.wrapping_sub(1)),
+                    (x, y + 1),
+                ];
+                for (nx, ny) in neighbours {
+                    if !map.in_bounds(nx, ny) || !open[nx + ny * width] {
+                        continue;
+                    }
+                    let i = nx + ny * width;
+                    if steps[i] == usize::MAX {
+                        steps[i] = next;
+                        labels.set(nx, ny, label);
+                        queue.push_back((nx, ny));
+                    } else if steps[i] == next && label < labels.get(nx, ny).flatten() {
+                        // Reached by another seed in the same number of steps
+                        labels.set(nx, ny, label);
+                    }
+                }
+            }
+        }
+    }
+    labels
+}
@@ -0,0 +1,177 @@
+//! Carving caves and tunnels into [`Map`][crate::Map]s with random walks.
+//!
+//! Walkers wander the map, turning each tile they step on into floor, until enough of the map is
+//! floor. To confine the walk to part of a map, carve into a
+//! [`MapRegionMut`][crate::MapRegionMut].
+
+use crate::{random::Rng, MapMut};
+
+/// Where each walker after the first starts. See [`Walk`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Spawn {
+    /// Every walker starts at the starting tile.
+    #[default]
+    Start,
+    /// Each walker starts on a random floor tile, so the carved area spreads out more evenly.
+    Floor,
+}
+
+/// Settings for [`carve()`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Walk {
+    /// The fraction of the map, from 0 to 1, that should be floor when the walk stops.
+    pub coverage: f32,
+    /// The maximum number of walkers, which walk one after another. The walk stops once they've
+    /// all finished, even if it hasn't reached [`Walk::coverage`].
+    pub walkers: usize,
+    /// The number of steps each walker takes before the next one starts.
+    pub steps: usize,
+    /// Where each walker after the first starts.
+    pub spawn: Spawn,
+    /// The direction (x, y) walkers prefer to step in, like `(1, 0)` to tunnel towards the right.
+    ///
+    /// Only the sign of each component matters. For a diagonal bias, like `(1, 1)`, walkers step
+    /// along one of the two axes at random, so they still only step horizontally or vertically.
+    pub bias: (isize, isize),
+    /// The chance, from 0 to 1, that a walker steps in the direction of `bias` rather than a
+    /// random direction.
+    pub bias_chance: f32,
+}
+
+impl Default for Walk {
+    /// A plain drunkard's walk, carving until 40% of the map is floor, with up to 100 walkers of
+    /// 400 steps each.
+    fn default() -> Self {
+        Self {
+            coverage: 0.4,
+            walkers: 100,
+            steps: 400,
+            spawn: Spawn::Start,
+            bias: (0, 0),
+            bias_chance: 0.0,
+        }
+    }
+}
+
+/// Carve floor into `map` with random walkers, starting from `start`.
+///
+/// Each walker steps horizontally or vertically, setting every tile it steps on to `floor`.
+/// Steps that would leave the map aren't taken. The walk stops as soon as the fraction of tiles
+/// that are floor, according to `is_floor`, reaches [`Walk::coverage`], or once every walker has
+/// finished.
+///
+/// Returns the number of floor tiles in the map afterwards. If `start` is out of bounds, nothing
+/// is carved.
+/// # Example
+/// ```
+/// use tile_maps::{random::Rng, row::StaticMap, walk::{self, Walk}, prelude::*};
+///
+/// let mut map = StaticMap::<char, 20, 20>::new();
+/// map.clear_to('#');
+/// let mut rng = Rng::new(42);
+/// let walk = Walk { coverage: 0.3, ..Walk::default() };
+///
+/// // Only carve into the left half of the map
+/// let mut left = map.region_mut(0, 0, 10, 20).unwrap();
+/// let floor = walk::carve(&mut left, &mut rng, (5, 10), &walk, '.', |&t| t == '.');
+/// assert_eq!(floor, 60);
+/// assert_eq!(map.get(5, 10), Some('.'));
+/// assert!((10..20).all(|x| (0..20).all(|y| map.get(x, y) == Some('#'))));
+///
+/// // Walkers pinned against the right edge can't reach the coverage, but still finish
+/// let mut map = StaticMap::<char, 20, 20>::new();
+/// map.clear_to('#');
+/// let tunnel = Walk { bias: (1, 0), bias_chance: 1.0, ..Walk::default() };
+/// let floor = walk::carve(&mut map, &mut rng, (0, 10), &tunnel, '.', |&t| t == '.');
+/// assert_eq!(floor, 20);
+/// ```
+pub fn carve<M, F>(
+    map: &mut M,
+    rng: &mut Rng,
+    start: (usize, usize),
+    walk: &Walk,
+    floor: M::Tile,
+    mut is_floor: F,
+) -> usize
+where
+    M: MapMut,
+    M::Tile: Clone,
+    F: FnMut(&M::Tile) -> bool,
+{
+    if !map.in_bounds(start.0, start.1) {
+        return 0;
+    }
+    let (width, height) = map.size();
+    let covered = |count: usize| count as f32 / (width * height) as f32 >= walk.coverage.min(1.0);
+    let mut count = 0;
+    for y in 0..height {
+        for x in 0..width {
+            if is_floor(map.get_ref(x, y).expect("Coordinates are in bounds")) {
+                count += 1;
+            }
+        }
+    }
+
+    for walker in 0..walk.walkers {
+        if covered(count) {
+            break;
+        }
+        let (mut x, mut y) = match walk.spawn {
+            Spawn::Floor if walker > 0 => random_floor(map, rng, &mut is_floor).unwrap_or(start),
+            _ => start,
+        };
+        for step in 0..=walk.steps {
+            if step > 0 {
+                let (dx, dy) = if rng.chance(walk.bias_chance) {
+                    match (walk.bias.0.signum(), walk.bias.1.signum()) {
+                        (dx, dy) if dx != 0 && dy != 0 => {
+                            if rng.chance(0.5) {
+                                (dx, 0)
+                            } else {
+                                (0, dy)
+                            }
+                        }
+                        bias => bias,
+                    }
+                } else {
+                    [(-1, 0), (1, 0), (0, -1), (0, 1)][rng.below(4)]
+                };
+                let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+                if map.in_bounds(nx, ny) {
+                    (x, y) = (nx, ny);
+                }
+            }
+            let tile = map.get_mut(x, y).expect("Walker is in bounds");
+            if !is_floor(tile) {
+                *tile = floor.clone();
+                count += 1;
+                if covered(count) {
+                    break;
+                }
+            }
+        }
+    }
+    count
+}
+
+/// Pick a floor tile uniformly at random, if there are any.
+fn random_floor<M, F>(map: &M, rng: &mut Rng, is_floor: &mut F) -> Option<(usize, usize)>
+where
+    M: MapMut,
+    F: FnMut(&M::Tile) -> bool,
+{
+    // Reservoir sampling, so no allocation is needed
+    let mut seen = 0;
+    let mut chosen = None;
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            if is_floor(map.get_ref(x, y).expect("Coordinates are in bounds")) {
+                seen += 1;
+                if rng.below(seen) == 0 {
+                    chosen = Some((x, y));
+                }
+            }
+        }
+    }
+    chosen
+}